@@ -0,0 +1,45 @@
+use crate::days;
+use common::Part;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Known answers for one input file of a day.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Contents of `answers.toml`, keyed by day (`day05`) and input name
+/// (`example`).
+#[derive(Debug, Default, Deserialize)]
+pub struct Answers(BTreeMap<String, BTreeMap<String, Expected>>);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let text = days::read(path)?;
+        toml::from_str(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn get(&self, day: u32, input: &str, part: Part) -> Option<&str> {
+        let expected = self.0.get(&day_key(day))?.get(input)?;
+        match part {
+            Part::One => expected.part1.as_deref(),
+            Part::Two => expected.part2.as_deref(),
+        }
+    }
+
+    /// Names of the inputs that have answers recorded for the given day.
+    pub fn inputs(&self, day: u32) -> impl Iterator<Item = &str> {
+        self.0
+            .get(&day_key(day))
+            .into_iter()
+            .flat_map(|inputs| inputs.keys().map(String::as_str))
+    }
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
@@ -0,0 +1,202 @@
+use crate::days;
+use common::{Part, Sample};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+pub struct Options {
+    pub runs: usize,
+    pub parts: Vec<Part>,
+    /// Relative increase of the median time or of the allocations that
+    /// counts as a regression. Allocations are counted for the whole
+    /// process, so days that spawn threads vary from run to run.
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Summary {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+    pub allocations: u64,
+}
+
+impl Summary {
+    fn of(samples: &[Sample]) -> Summary {
+        let mut times: Vec<_> = samples.iter().map(|s| s.time.as_nanos() as u64).collect();
+        let mut allocations: Vec<_> = samples.iter().map(|s| s.allocations).collect();
+        times.sort_unstable();
+        allocations.sort_unstable();
+
+        let p95 = (times.len() * 95).div_ceil(100).max(1) - 1;
+        Summary {
+            min_ns: times[0],
+            median_ns: times[(times.len() - 1) / 2],
+            p95_ns: times[p95],
+            allocations: allocations[(allocations.len() - 1) / 2],
+        }
+    }
+
+    /// Describes how `self` got worse than `baseline` by more than
+    /// `threshold`, if it did.
+    fn regression(&self, baseline: &Summary, threshold: f64) -> Option<String> {
+        let exceeds = |now: u64, before: u64| now as f64 > before as f64 * (1.0 + threshold);
+        if exceeds(self.median_ns, baseline.median_ns) {
+            let change = 100.0 * (self.median_ns as f64 / baseline.median_ns as f64 - 1.0);
+            Some(format!("median +{:.0}%", change))
+        } else if exceeds(self.allocations, baseline.allocations) {
+            Some(format!(
+                "allocations {} -> {}",
+                baseline.allocations, self.allocations
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Benchmark results keyed by day (`day05`) and stage (`part2`).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline(BTreeMap<String, BTreeMap<String, Summary>>);
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, String> {
+        let text = days::read(path)?;
+        toml::from_str(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|err| err.to_string())?;
+        fs::write(path, text).map_err(|err| format!("cannot write {}: {}", path.display(), err))
+    }
+
+    /// Adds the results of `other`, replacing the stages it also measured.
+    pub fn merge(&mut self, other: Baseline) {
+        for (day, stages) in other.0 {
+            self.0.entry(day).or_default().extend(stages);
+        }
+    }
+
+    fn get(&self, day: &str, stage: &str) -> Option<&Summary> {
+        self.0.get(day)?.get(stage)
+    }
+
+    fn insert(&mut self, day: String, stage: String, summary: Summary) {
+        self.0.entry(day).or_default().insert(stage, summary);
+    }
+}
+
+/// Benchmarks every stage of the given days on their `input.txt`, printing a
+/// table and comparing against `baseline` when there is one. Returns the
+/// results and whether anything regressed.
+pub fn bench(
+    day_numbers: &[u32],
+    options: &Options,
+    baseline: Option<&Baseline>,
+) -> Result<(Baseline, bool), String> {
+    let mut results = Baseline::default();
+    let mut regressed = false;
+
+    println!(
+        "{:<6} {:<6} {:>12} {:>12} {:>12} {:>8}",
+        "day", "stage", "min", "median", "p95", "allocs"
+    );
+    for &day in day_numbers {
+        let solver = days::find(day).ok_or_else(|| format!("day {} is not solved yet", day))?;
+        let path = days::input_path(day, None);
+        let input = days::read(&path)?;
+        let stages = solver
+            .bench(&input, &options.parts, options.runs)
+            .map_err(|err| err.in_file(&path).to_string())?;
+
+        for (stage, samples) in stages {
+            let (day, stage) = (format!("day{:02}", day), stage.to_string());
+            let summary = Summary::of(&samples);
+            print!(
+                "{:<6} {:<6} {:>12} {:>12} {:>12} {:>8}",
+                day,
+                stage,
+                format_ns(summary.min_ns),
+                format_ns(summary.median_ns),
+                format_ns(summary.p95_ns),
+                summary.allocations
+            );
+            let regression = baseline
+                .and_then(|baseline| baseline.get(&day, &stage))
+                .and_then(|old| summary.regression(old, options.threshold));
+            match regression {
+                Some(regression) => {
+                    regressed = true;
+                    println!("  REGRESSED ({})", regression);
+                }
+                None => println!(),
+            }
+            results.insert(day, stage, summary);
+        }
+    }
+    Ok((results, regressed))
+}
+
+fn format_ns(ns: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(ns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(times: impl IntoIterator<Item = u64>) -> Vec<Sample> {
+        times
+            .into_iter()
+            .map(|ns| Sample {
+                time: Duration::from_nanos(ns),
+                allocations: ns % 3,
+            })
+            .collect()
+    }
+
+    fn summary(median_ns: u64, allocations: u64) -> Summary {
+        Summary {
+            min_ns: median_ns,
+            median_ns,
+            p95_ns: median_ns,
+            allocations,
+        }
+    }
+
+    #[test]
+    fn percentiles() {
+        let one = Summary::of(&samples([7]));
+        assert_eq!((one.min_ns, one.median_ns, one.p95_ns), (7, 7, 7));
+
+        let ten = Summary::of(&samples([4, 9, 1, 10, 2, 8, 3, 7, 5, 6]));
+        assert_eq!((ten.min_ns, ten.median_ns, ten.p95_ns), (1, 5, 10));
+
+        let twenty = Summary::of(&samples((1..=20).rev()));
+        assert_eq!(
+            (twenty.min_ns, twenty.median_ns, twenty.p95_ns),
+            (1, 10, 19)
+        );
+
+        let hundred = Summary::of(&samples(1..=100));
+        assert_eq!((hundred.median_ns, hundred.p95_ns), (50, 95));
+        assert_eq!(hundred.allocations, 1);
+    }
+
+    #[test]
+    fn regressions_beyond_the_threshold() {
+        let baseline = summary(1000, 100);
+        assert_eq!(summary(1200, 100).regression(&baseline, 0.2), None);
+        assert_eq!(
+            summary(1300, 100).regression(&baseline, 0.2),
+            Some("median +30%".to_string())
+        );
+        assert_eq!(summary(900, 115).regression(&baseline, 0.2), None);
+        assert_eq!(
+            summary(900, 130).regression(&baseline, 0.2),
+            Some("allocations 100 -> 130".to_string())
+        );
+    }
+}
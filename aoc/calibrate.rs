@@ -0,0 +1,137 @@
+use day01::{Dictionary, Match, NoDigits, Scanner, StreamError, Summary};
+use std::cmp::Reverse;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::days;
+
+pub struct Options<'a> {
+    /// Words to recognise besides the numerals, English ones by default.
+    pub dictionary: Option<&'a Path>,
+    pub ignore_case: bool,
+    /// Recognise decimal digits of every script, not just ASCII ones.
+    pub unicode: bool,
+    pub no_digits: NoDigits,
+    /// Print the words found on every line before the sum.
+    pub explain: bool,
+    /// Highlight the words in explanations with ANSI colours.
+    pub color: bool,
+    /// Decode chunks of the document on all cores.
+    pub parallel: bool,
+}
+
+/// Sums the calibration values of a day 1 document, `-` being standard
+/// input. The document is streamed unless it needs explaining.
+pub fn calibrate(input: Option<&Path>, options: &Options) -> Result<Summary, String> {
+    let scanner = Scanner::new(&dictionary(options)?);
+    let path = days::input_path(1, input);
+    let (name, mut reader): (_, Box<dyn BufRead>) = if path == Path::new("-") {
+        (Path::new("<stdin>"), Box::new(io::stdin().lock()))
+    } else {
+        let file =
+            File::open(&path).map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+        (path.as_path(), Box::new(BufReader::new(file)))
+    };
+
+    if options.explain {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|err| format!("cannot read {}: {}", name.display(), err))?;
+        explain(&text, &scanner, options.color);
+        return day01::calibrate(&text, &scanner, options.no_digits)
+            .map_err(|err| err.in_file(name).to_string());
+    }
+    day01::calibrate_reader(reader, &scanner, options.no_digits, options.parallel).map_err(|err| {
+        match err {
+            StreamError::Io(err) => format!("cannot read {}: {}", name.display(), err),
+            StreamError::Parse(err) => err.in_file(name).to_string(),
+        }
+    })
+}
+
+fn dictionary(options: &Options) -> Result<Dictionary, String> {
+    let dictionary = match options.dictionary {
+        Some(path) => {
+            let words = Dictionary::parse(&days::read(path)?)
+                .map_err(|err| err.in_file(path).to_string())?;
+            Dictionary::numerals().extend(words)
+        }
+        None => Dictionary::english(),
+    };
+    let dictionary = dictionary.ignore_case(options.ignore_case);
+    Ok(if options.unicode {
+        dictionary.unicode_numerals()
+    } else {
+        dictionary
+    })
+}
+
+/// Prints every line with its calibration value, followed by the words
+/// found on it: byte offset, kind, text, digits and whether it is the word
+/// the first or last digit was taken from.
+fn explain(text: &str, scanner: &Scanner, color: bool) {
+    for (i, line) in text.lines().enumerate() {
+        let mut matches: Vec<Match> = scanner.matches(line).collect();
+        matches.sort_by_key(|m| (m.start, Reverse(m.end)));
+        let ends = scanner.first_and_last_matches(line);
+        let value = scanner
+            .first_and_last(line)
+            .map_or("-".to_string(), |(first, last)| {
+                (first * 10 + last).to_string()
+            });
+
+        println!(
+            "{:>5}  {}  => {}",
+            i + 1,
+            highlight(line, &matches, ends, color),
+            value
+        );
+        for m in &matches {
+            let kind = if m.numeral { "numeral" } else { "word" };
+            let digits: String = m.digits.iter().map(u32::to_string).collect();
+            let role = match ends {
+                Some((first, last)) if *m == first && *m == last => "first, last",
+                Some((first, _)) if *m == first => "first",
+                Some((_, last)) if *m == last => "last",
+                _ => "",
+            };
+            let row = format!(
+                "       {:>4}  {:<7}  {:<10} {:<4} {}",
+                m.start,
+                kind,
+                &line[m.start..m.end],
+                digits,
+                role
+            );
+            println!("{}", row.trim_end());
+        }
+    }
+}
+
+/// The line with the first and last word in bold green and other words in
+/// cyan, or the plain line without `color`.
+fn highlight(line: &str, matches: &[Match], ends: Option<(Match, Match)>, color: bool) -> String {
+    if !color {
+        return line.to_string();
+    }
+    let covers = |m: &Match, i: usize| (m.start..m.end).contains(&i);
+
+    let mut highlighted = String::new();
+    let mut current = "";
+    for (i, c) in line.char_indices() {
+        let style = match ends {
+            Some((first, last)) if covers(&first, i) || covers(&last, i) => "\x1b[1;32m",
+            _ if matches.iter().any(|m| covers(m, i)) => "\x1b[36m",
+            _ => "\x1b[0m",
+        };
+        if style != current {
+            highlighted.push_str(style);
+            current = style;
+        }
+        highlighted.push(c);
+    }
+    highlighted.push_str("\x1b[0m");
+    highlighted
+}
@@ -0,0 +1,118 @@
+use crate::config::Config;
+use std::fs;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// HTTP client for the puzzle website. Every request carries the session
+/// cookie and waits until at least `throttle_seconds` have passed since the
+/// previous request, including requests made by earlier runs.
+pub struct Client<'a> {
+    config: &'a Config,
+    agent: ureq::Agent,
+}
+
+impl<'a> Client<'a> {
+    pub fn new(config: &'a Config) -> Client<'a> {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(concat!(
+                "github.com/nablaa/advent-of-code-2023 aoc/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .timeout(Duration::from_secs(30))
+            .build();
+        Client { config, agent }
+    }
+
+    pub fn config(&self) -> &Config {
+        self.config
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, String> {
+        let request = self.request("GET", path)?;
+        read_response(path, request.call())
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        let request = self.request("POST", path)?;
+        read_response(path, request.send_form(form))
+    }
+
+    fn request(&self, method: &str, path: &str) -> Result<ureq::Request, String> {
+        let session = self
+            .config
+            .session
+            .as_deref()
+            .ok_or("no session cookie: set AOC_SESSION or `session` in aoc.toml".to_string())?;
+        self.throttle()?;
+        let url = format!("{}{}", self.config.base_url, path);
+        Ok(self
+            .agent
+            .request(method, &url)
+            .set("Cookie", &format!("session={}", session)))
+    }
+
+    fn throttle(&self) -> Result<(), String> {
+        let stamp = self.config.state_dir.join("last-request");
+        let interval = Duration::from_secs(self.config.throttle_seconds);
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|text| text.trim().parse().ok())
+            .map(Duration::from_millis);
+
+        if let Some(last) = last {
+            if let Some(wait) = (last + interval).checked_sub(now()) {
+                thread::sleep(wait);
+            }
+        }
+
+        fs::create_dir_all(&self.config.state_dir)
+            .and_then(|_| fs::write(&stamp, now().as_millis().to_string()))
+            .map_err(|err| format!("cannot write {}: {}", stamp.display(), err))
+    }
+}
+
+fn read_response(
+    path: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|err| format!("{}: {}", path, err)),
+        Err(ureq::Error::Status(404, _)) => {
+            Err(format!("{}: not found, is it unlocked yet?", path))
+        }
+        Err(ureq::Error::Status(code, _)) => Err(format!("{}: server answered {}", path, code)),
+        Err(err) => Err(format!("{}: {}", path, err)),
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::stub::{self, Server};
+    use std::time::Instant;
+
+    #[test]
+    fn requests_are_throttled_across_clients() {
+        let server = Server::start(|_, _| (200, String::new()));
+        let state_dir = stub::state_dir("throttle");
+        let config = Config {
+            throttle_seconds: 1,
+            ..stub::config(&server, &state_dir)
+        };
+
+        let start = Instant::now();
+        Client::new(&config).get("/").unwrap();
+        Client::new(&config).get("/").unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(900));
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::days;
+use serde::Deserialize;
+use std::env;
+use std::path::PathBuf;
+
+/// Settings for talking to the puzzle website, read from `aoc.toml` in the
+/// workspace. `AOC_SESSION` and `AOC_BASE_URL` override the file.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    pub base_url: String,
+    pub year: u32,
+    /// Minimum number of seconds between two requests.
+    pub throttle_seconds: u64,
+    /// Where request timestamps and other local state are kept.
+    pub state_dir: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            session: None,
+            base_url: "https://adventofcode.com".to_string(),
+            year: 2023,
+            throttle_seconds: 5,
+            state_dir: days::workspace().join(".aoc"),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Config, String> {
+        let path = days::workspace().join("aoc.toml");
+        let mut config: Config = if path.exists() {
+            let text = days::read(&path)?;
+            toml::from_str(&text).map_err(|err| format!("{}: {}", path.display(), err))?
+        } else {
+            Config::default()
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session).filter(|session| !session.is_empty());
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = base_url;
+        }
+        config.base_url = config.base_url.trim_end_matches('/').to_string();
+        Ok(config)
+    }
+}
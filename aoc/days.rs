@@ -0,0 +1,38 @@
+use common::Solver;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DAYS: &[(u32, &dyn Solver)] = &[
+    (1, &day01::Day01),
+    (2, &day02::Day02),
+    (3, &day03::Day03),
+    (4, &day04::Day04),
+    (5, &day05::Day05),
+    (6, &day06::Day06),
+];
+
+pub fn find(number: u32) -> Option<&'static dyn Solver> {
+    DAYS.iter()
+        .find(|(day, _)| *day == number)
+        .map(|(_, solver)| *solver)
+}
+
+/// Root of the workspace the runner was built from.
+pub fn workspace() -> PathBuf {
+    let runner = Path::new(env!("CARGO_MANIFEST_DIR"));
+    runner.parent().unwrap_or(runner).to_path_buf()
+}
+
+/// Directory of the given day inside the workspace, e.g. `day05/`.
+pub fn directory(number: u32) -> PathBuf {
+    workspace().join(format!("day{:02}", number))
+}
+
+/// The input given on the command line, or the day's `input.txt`.
+pub fn input_path(number: u32, input: Option<&Path>) -> PathBuf {
+    input.map_or_else(|| directory(number).join("input.txt"), Path::to_path_buf)
+}
+
+pub fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path.display(), err))
+}
@@ -0,0 +1,79 @@
+use crate::client::Client;
+use crate::days;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the day's `input.txt` exists, downloading it only when there
+/// is no non-empty copy on disk yet.
+pub fn fetch(day: u32, client: &Client) -> Result<Fetched, String> {
+    fetch_into(&days::directory(day), day, client)
+}
+
+fn fetch_into(directory: &Path, day: u32, client: &Client) -> Result<Fetched, String> {
+    let path = directory.join("input.txt");
+    if fs::metadata(&path).is_ok_and(|meta| meta.len() > 0) {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.get(&format!("/{}/day/{}/input", client.config().year, day))?;
+
+    let partial = directory.join("input.txt.part");
+    fs::create_dir_all(directory)
+        .and_then(|_| fs::write(&partial, input))
+        .and_then(|_| fs::rename(&partial, &path))
+        .map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{self, Server};
+
+    #[test]
+    fn downloads_a_day_once() {
+        let server = Server::start(|_, path| match path {
+            "/2023/day/1/input" => (200, "1abc2\n".to_string()),
+            _ => (404, "404 Not Found".to_string()),
+        });
+        let state_dir = stub::state_dir("fetch");
+        let config = stub::config(&server, &state_dir);
+        let client = Client::new(&config);
+        let directory = config.state_dir.join("day01");
+
+        let Ok(Fetched::Downloaded(path)) = fetch_into(&directory, 1, &client) else {
+            panic!("day 1 was not downloaded");
+        };
+        assert_eq!(fs::read_to_string(path).unwrap(), "1abc2\n");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/2023/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+
+        assert!(matches!(
+            fetch_into(&directory, 1, &client),
+            Ok(Fetched::Cached(_))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn locked_days_are_not_found() {
+        let server = Server::start(|_, _| (404, "404 Not Found".to_string()));
+        let state_dir = stub::state_dir("fetch-locked");
+        let config = stub::config(&server, &state_dir);
+        let client = Client::new(&config);
+        let directory = config.state_dir.join("day25");
+
+        let Err(err) = fetch_into(&directory, 25, &client) else {
+            panic!("day 25 was downloaded");
+        };
+        assert!(err.contains("unlocked"), "{}", err);
+        assert!(!directory.join("input.txt").exists());
+    }
+}
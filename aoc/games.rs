@@ -0,0 +1,139 @@
+use common::Solution;
+use day02::{Bag, Day02, Generator, Measure, Query};
+use std::path::Path;
+
+use crate::days;
+
+/// Checks every game of a day 2 log against each bag, printing the possible
+/// games and why the others are not.
+pub fn bags(input: Option<&Path>, specs: &[String], file: Option<&Path>) -> Result<(), String> {
+    let mut bags = Vec::new();
+    if let Some(path) = file {
+        let text = days::read(path)?;
+        bags = day02::parse_bags(&text).map_err(|err| err.in_file(path).to_string())?;
+    }
+    for spec in specs {
+        let bag = Bag::parse(spec).map_err(|err| err.in_file("--bag").to_string())?;
+        bags.push((spec.clone(), bag));
+    }
+    if bags.is_empty() {
+        bags.push(("part 1".to_string(), Bag::part1()));
+    }
+
+    let path = days::input_path(2, input);
+    let games = Day02::parse(&days::read(&path)?).map_err(|err| err.in_file(&path).to_string())?;
+
+    for (name, bag) in &bags {
+        println!("{}  ({})", name, bag);
+        let possible: Vec<_> = games.iter().filter(|game| bag.allows(game)).collect();
+        if possible.is_empty() {
+            println!("  possible   none");
+        } else {
+            let ids: Vec<_> = possible.iter().map(|game| game.id().to_string()).collect();
+            let sum: u32 = possible.iter().map(|game| game.id()).sum();
+            println!("  possible   {}  (sum {})", ids.join(", "), sum);
+        }
+        for game in &games {
+            if let Err(violation) = bag.check(game) {
+                println!("  game {:<5} {}", game.id(), violation);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints statistics of a day 2 log and the bags they point to.
+pub fn report(input: Option<&Path>, cap: u32) -> Result<(), String> {
+    let path = days::input_path(2, input);
+    let games = Day02::parse(&days::read(&path)?).map_err(|err| err.in_file(&path).to_string())?;
+    let report = day02::analyse(&games, cap);
+
+    println!("{} games, {} sets\n", report.games, report.sets);
+    println!("colour      shown   mean   max  cubes: sets");
+    for distribution in &report.distributions {
+        let shown = distribution.sets() - distribution.histogram.get(&0).copied().unwrap_or(0);
+        let histogram: Vec<_> = distribution
+            .histogram
+            .iter()
+            .map(|(count, sets)| format!("{}:{}", count, sets))
+            .collect();
+        println!(
+            "{:<10} {:>6} {:>6.2} {:>5}  {}",
+            distribution.colour,
+            shown,
+            distribution.mean(),
+            distribution.max(),
+            histogram.join(" ")
+        );
+    }
+
+    println!("\nminimal bag     {}", report.minimal_bag);
+    for (colour, ids) in &report.binding {
+        let ids: Vec<_> = ids.iter().map(u32::to_string).collect();
+        println!("  {:<13} binding in games {}", colour, ids.join(", "));
+    }
+    let estimate = &report.estimate;
+    println!(
+        "estimated bag   {}  (log-likelihood {:.2})",
+        estimate.bag, estimate.log_likelihood
+    );
+    if estimate.capped {
+        println!(
+            "  the likelihood was still rising at the cap of {} cubes, so only the ratios between colours mean something",
+            cap
+        );
+    }
+    Ok(())
+}
+
+/// Prints the ids of the day 2 games matching `query`, or with `sum` the
+/// sum of that measure over them.
+pub fn query(query: &str, input: Option<&Path>, sum: Option<&str>) -> Result<(), String> {
+    let query = Query::parse(query).map_err(|err| err.in_file("<query>").to_string())?;
+    let sum = sum
+        .map(|sum| Measure::parse(sum).map_err(|err| err.in_file("--sum").to_string()))
+        .transpose()?;
+    let path = days::input_path(2, input);
+    let games = Day02::parse(&days::read(&path)?).map_err(|err| err.in_file(&path).to_string())?;
+
+    let matching = games.iter().filter(|game| query.matches(game));
+    match sum {
+        Some(measure) => println!("{}", matching.map(|game| measure.of(game)).sum::<u64>()),
+        None => matching.for_each(|game| println!("{}", game.id())),
+    }
+    Ok(())
+}
+
+/// Prints a synthetic day 2 log drawn from `bag`, the part 1 bag by default.
+pub fn generate(mut generator: Generator, bag: Option<&str>) -> Result<(), String> {
+    if let Some(bag) = bag {
+        generator.bag = Bag::parse(bag).map_err(|err| err.in_file("--bag").to_string())?;
+    }
+    let (log, _) = generator.generate()?;
+    print!("{}", log);
+    Ok(())
+}
+
+/// Parses a day 2 log strictly and prints every problem found, returning
+/// whether there were none.
+pub fn lint(input: Option<&Path>, colours: &[String]) -> Result<bool, String> {
+    let colours: Vec<&str> = if colours.is_empty() {
+        day02::COLOURS.to_vec()
+    } else {
+        colours.iter().map(String::as_str).collect()
+    };
+    let path = days::input_path(2, input);
+    match day02::parse_games_strict(&days::read(&path)?, &colours) {
+        Ok(games) => {
+            println!("{} games, no problems", games.len());
+            Ok(true)
+        }
+        Err(errors) => {
+            for error in &errors {
+                println!("{}\n", error.clone().in_file(&path));
+            }
+            println!("{} problems", errors.len());
+            Ok(false)
+        }
+    }
+}
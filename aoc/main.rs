@@ -0,0 +1,515 @@
+use clap::{Parser, Subcommand};
+use common::Part;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[global_allocator]
+static ALLOCATOR: common::CountingAllocator = common::CountingAllocator;
+
+mod answers;
+mod bench;
+mod calibrate;
+mod client;
+mod config;
+mod days;
+mod fetch;
+mod games;
+mod output;
+mod scaffold;
+mod schematic;
+#[cfg(test)]
+mod stub;
+mod submit;
+mod verify;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve days' puzzles
+    Run {
+        /// Day numbers, e.g. 5, all days by default
+        days: Vec<u32>,
+        /// Only solve the given part
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        /// Puzzle input to use instead of the day's `input.txt`
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Print bare answers or one JSON object per part
+        #[arg(long, value_enum, default_value_t = output::Format::Text)]
+        format: output::Format,
+    },
+    /// Check the answers of every day against `answers.toml`
+    Verify {
+        /// Days to check, all of them by default
+        days: Vec<u32>,
+        /// Answers file to compare against
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Time the parse, part 1 and part 2 stages of every day
+    Bench {
+        /// Days to benchmark, all of them by default
+        days: Vec<u32>,
+        /// Only benchmark the given part (parsing is always benchmarked)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        /// How many times to run each stage
+        #[arg(long, default_value_t = 10, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        runs: usize,
+        /// Baseline to compare against [default: bench-baseline.toml]
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Store the results in the baseline instead of only comparing
+        #[arg(long)]
+        save_baseline: bool,
+        /// Relative increase of the median time or of the allocations that
+        /// counts as a regression
+        #[arg(long, default_value_t = 0.2)]
+        threshold: f64,
+    },
+    /// Download puzzle inputs that are not on disk yet
+    Fetch {
+        /// Days to download
+        #[arg(required = true)]
+        days: Vec<u32>,
+    },
+    /// Send an answer to the website and record the verdict
+    Submit {
+        /// Day number, e.g. 5
+        day: u32,
+        /// Part to answer
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        /// Answer to send instead of solving the day's `input.txt`
+        #[arg(long)]
+        answer: Option<String>,
+    },
+    /// Create a new day from the template, register it with the runner and
+    /// add an example table to answers.toml
+    New {
+        /// Day number, e.g. 7
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+    /// Sum day 1 calibration values with a custom digit dictionary
+    Calibrate {
+        /// Calibration document or `-` for standard input, day 1's
+        /// `input.txt` by default
+        input: Option<PathBuf>,
+        /// File of `word number` lines to recognise besides the numerals
+        /// [default: English words one to nine]
+        #[arg(long)]
+        dictionary: Option<PathBuf>,
+        /// Match words regardless of case
+        #[arg(long)]
+        ignore_case: bool,
+        /// Also recognise non-ASCII decimal digits such as `７` or `٧`
+        #[arg(long)]
+        unicode: bool,
+        /// What to do with lines without digits: error, skip or zero
+        #[arg(long, default_value = "error")]
+        no_digits: day01::NoDigits,
+        /// List the words found on every line and the value they make up
+        #[arg(long)]
+        explain: bool,
+        /// Highlight the words in the explanation with ANSI colours
+        #[arg(long, requires = "explain")]
+        color: bool,
+        /// Decode chunks of the document on all cores
+        #[arg(long, conflicts_with = "explain")]
+        parallel: bool,
+    },
+    /// List the day 2 games that are possible with each bag [default: the
+    /// part 1 bag]
+    Bags {
+        /// Game log, day 2's `input.txt` by default
+        input: Option<PathBuf>,
+        /// Cubes in a bag, e.g. "12 red, 13 green, 14 blue"; can be repeated
+        #[arg(long = "bag")]
+        bags: Vec<String>,
+        /// File with one `name: cubes` bag per line
+        #[arg(long)]
+        bags_file: Option<PathBuf>,
+    },
+    /// Summarise a day 2 game log and estimate the bag it was played with
+    Report {
+        /// Game log, day 2's `input.txt` by default
+        input: Option<PathBuf>,
+        /// Most cubes of one colour the estimated bag may hold; most logs
+        /// reach it, leaving only the ratios between colours meaningful
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..=1_000_000))]
+        cap: u32,
+    },
+    /// Print the ids of the day 2 games matching a query
+    Query {
+        /// Condition such as "any(blue > 10) and all(red <= 3)" or
+        /// "max(green) >= 5"
+        query: String,
+        /// Game log, day 2's `input.txt` by default
+        input: Option<PathBuf>,
+        /// Print the sum of a game value such as `id` or `power` over the
+        /// matching games instead
+        #[arg(long)]
+        sum: Option<String>,
+    },
+    /// Write a random day 2 game log to standard output
+    Generate {
+        /// Number of games
+        #[arg(long, default_value_t = 100)]
+        games: usize,
+        /// Most sets per game
+        #[arg(long, default_value_t = 6, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        max_sets: usize,
+        /// Cubes in the bag, which are also the colours used [default: "12
+        /// red, 13 green, 14 blue"]
+        #[arg(long)]
+        bag: Option<String>,
+        /// Share of games that show more cubes than the bag holds
+        #[arg(long, default_value_t = 0.3)]
+        impossible: f64,
+        /// Seed for the random numbers; the same seed gives the same log
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// List the numbers of a day 3 schematic and the symbols touching them
+    Numbers {
+        /// Schematic, day 3's `input.txt` by default
+        input: Option<PathBuf>,
+        /// Only list part numbers or only the other numbers
+        #[arg(long, value_enum, default_value_t = schematic::Filter::All)]
+        only: schematic::Filter,
+        /// Characters that are neither digits nor symbols
+        #[arg(long, default_value = ".")]
+        blanks: String,
+    },
+    /// List the gears of a day 3 schematic [default: `*` touching exactly
+    /// two numbers, multiplied]
+    Gears {
+        /// Schematic, day 3's `input.txt` by default
+        input: Option<PathBuf>,
+        /// Symbols that can be gears
+        #[arg(long, default_value = "*")]
+        symbols: String,
+        /// Exact number of numbers a gear touches
+        #[arg(long, conflicts_with_all = ["min", "max"])]
+        exactly: Option<usize>,
+        /// Fewest numbers a gear touches [default: 1 with --max]
+        #[arg(long)]
+        min: Option<usize>,
+        /// Most numbers a gear touches [default: unlimited with --min]
+        #[arg(long)]
+        max: Option<usize>,
+        /// How the numbers make up the ratio: product, sum or max
+        #[arg(long, default_value = "product")]
+        reduce: day03::Reducer,
+        /// Characters that are neither digits nor symbols
+        #[arg(long, default_value = ".")]
+        blanks: String,
+    },
+    /// Summarise the numbers touching each kind of symbol in a day 3
+    /// schematic
+    Symbols {
+        /// Schematic, day 3's `input.txt` by default
+        input: Option<PathBuf>,
+        /// Characters that are neither digits nor symbols
+        #[arg(long, default_value = ".")]
+        blanks: String,
+    },
+    /// Check a day 2 game log strictly and list every malformed entry
+    Lint {
+        /// Game log, day 2's `input.txt` by default
+        input: Option<PathBuf>,
+        /// Allowed cube colour; can be repeated [default: red, green, blue]
+        #[arg(long = "colour")]
+        colours: Vec<String>,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            days,
+            part,
+            input,
+            format,
+        } => run(days, part, input, format),
+        Command::Verify { days, answers } => verify(days, answers),
+        Command::Bench {
+            days,
+            part,
+            runs,
+            baseline,
+            save_baseline,
+            threshold,
+        } => {
+            let options = bench::Options {
+                runs,
+                parts: parts(part),
+                threshold,
+            };
+            match bench(days, &options, baseline, save_baseline) {
+                Ok(false) => ExitCode::FAILURE,
+                result => exit(result.map(|_| ())),
+            }
+        }
+        Command::Fetch { days } => fetch(days),
+        Command::Submit { day, part, answer } => match submit(day, part, answer) {
+            Ok(false) => ExitCode::FAILURE,
+            result => exit(result.map(|_| ())),
+        },
+        Command::New { day } => new_day(day),
+        Command::Calibrate {
+            input,
+            dictionary,
+            ignore_case,
+            unicode,
+            no_digits,
+            explain,
+            color,
+            parallel,
+        } => {
+            let options = calibrate::Options {
+                dictionary: dictionary.as_deref(),
+                ignore_case,
+                unicode,
+                no_digits,
+                explain,
+                color,
+                parallel,
+            };
+            calibrate(input, &options)
+        }
+        Command::Report { input, cap } => exit(games::report(input.as_deref(), cap)),
+        Command::Query { query, input, sum } => {
+            exit(games::query(&query, input.as_deref(), sum.as_deref()))
+        }
+        Command::Generate {
+            games,
+            max_sets,
+            bag,
+            impossible,
+            seed,
+        } => {
+            let generator = day02::Generator {
+                games,
+                max_sets,
+                impossible,
+                seed,
+                ..Default::default()
+            };
+            exit(games::generate(generator, bag.as_deref()))
+        }
+        Command::Numbers {
+            input,
+            only,
+            blanks,
+        } => exit(schematic::numbers(input.as_deref(), only, &blanks)),
+        Command::Gears {
+            input,
+            symbols,
+            exactly,
+            min,
+            max,
+            reduce,
+            blanks,
+        } => {
+            let (min, max) = match (exactly, min, max) {
+                (Some(n), _, _) => (n, n),
+                (None, None, None) => (2, 2),
+                (None, min, max) => (min.unwrap_or(1), max.unwrap_or(usize::MAX)),
+            };
+            exit(
+                day03::GearRule::new(symbols.chars().collect(), min, max, reduce)
+                    .and_then(|rule| schematic::gears(input.as_deref(), &rule, &blanks)),
+            )
+        }
+        Command::Symbols { input, blanks } => exit(schematic::symbols(input.as_deref(), &blanks)),
+        Command::Lint { input, colours } => match games::lint(input.as_deref(), &colours) {
+            Ok(false) => ExitCode::FAILURE,
+            result => exit(result.map(|_| ())),
+        },
+        Command::Bags {
+            input,
+            bags,
+            bags_file,
+        } => exit(games::bags(input.as_deref(), &bags, bags_file.as_deref())),
+    }
+}
+
+/// Exit status of a command, printing its error if it failed.
+fn exit(result: Result<(), String>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("aoc: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(
+    day_numbers: Vec<u32>,
+    part: Option<u32>,
+    input: Option<PathBuf>,
+    format: output::Format,
+) -> ExitCode {
+    if input.is_some() && day_numbers.len() != 1 {
+        eprintln!("aoc: --input needs exactly one day");
+        return ExitCode::FAILURE;
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    for day in all_days(day_numbers) {
+        let path = days::input_path(day, input.as_deref());
+        for record in output::solve(day, &parts(part), &path) {
+            output::print(&record, format);
+            if !record.diagnostics.is_empty() {
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+/// Parts selected by an optional `--part` flag.
+fn parts(part: Option<u32>) -> Vec<Part> {
+    match part.and_then(Part::from_number) {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+fn all_days(day_numbers: Vec<u32>) -> Vec<u32> {
+    if day_numbers.is_empty() {
+        days::DAYS.iter().map(|(day, _)| *day).collect()
+    } else {
+        day_numbers
+    }
+}
+
+fn verify(day_numbers: Vec<u32>, answers: Option<PathBuf>) -> ExitCode {
+    let path = answers.unwrap_or_else(|| days::workspace().join("answers.toml"));
+    let answers = match answers::Answers::load(&path) {
+        Ok(answers) => answers,
+        Err(err) => return exit(Err(err)),
+    };
+    if verify::verify(&all_days(day_numbers), &answers) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Benchmarks the given days, returning whether nothing regressed.
+fn bench(
+    day_numbers: Vec<u32>,
+    options: &bench::Options,
+    baseline: Option<PathBuf>,
+    save_baseline: bool,
+) -> Result<bool, String> {
+    let path = baseline.unwrap_or_else(|| days::workspace().join("bench-baseline.toml"));
+    let stored = if path.exists() {
+        Some(bench::Baseline::load(&path)?)
+    } else {
+        None
+    };
+
+    let compare_to = if save_baseline { None } else { stored.as_ref() };
+    let (results, regressed) = bench::bench(&all_days(day_numbers), options, compare_to)?;
+
+    if save_baseline {
+        let mut baseline = stored.unwrap_or_default();
+        baseline.merge(results);
+        baseline.save(&path)?;
+        println!("\nsaved baseline to {}", path.display());
+    }
+    Ok(!regressed)
+}
+
+fn fetch(day_numbers: Vec<u32>) -> ExitCode {
+    let config = match config::Config::load() {
+        Ok(config) => config,
+        Err(err) => return exit(Err(err)),
+    };
+    let client = client::Client::new(&config);
+
+    let mut status = ExitCode::SUCCESS;
+    for day in day_numbers {
+        match fetch::fetch(day, &client) {
+            Ok(fetch::Fetched::Cached(path)) => {
+                println!("day{:02}  cached      {}", day, path.display())
+            }
+            Ok(fetch::Fetched::Downloaded(path)) => {
+                println!("day{:02}  downloaded  {}", day, path.display())
+            }
+            Err(err) => {
+                eprintln!("aoc: day {}: {}", day, err);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
+
+/// Submits an answer, the one computed from the day's input by default,
+/// returning whether it was correct.
+fn submit(day: u32, part: u32, answer: Option<String>) -> Result<bool, String> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => solve_input(day, Part::from_number(part).unwrap_or(Part::One))?,
+    };
+    let config = config::Config::load()?;
+    let client = client::Client::new(&config);
+
+    let verdict = submit::submit(&client, day, part, &answer)?;
+    println!("day{:02} part {}: {} is {}", day, part, answer, verdict);
+    Ok(verdict == submit::Verdict::Correct)
+}
+
+/// Solves one part of the day's `input.txt`.
+fn solve_input(day: u32, part: Part) -> Result<String, String> {
+    let solver = days::find(day).ok_or_else(|| format!("day {} is not solved yet", day))?;
+    let path = days::input_path(day, None);
+    let input = days::read(&path)?;
+    let run = solver
+        .run(&input, part)
+        .map_err(|err| err.in_file(&path).to_string())?;
+    Ok(run.answer)
+}
+
+fn new_day(day: u32) -> ExitCode {
+    exit(scaffold::new_day(day).map(|written| {
+        for path in written {
+            println!("wrote {}", path.display());
+        }
+    }))
+}
+
+fn calibrate(input: Option<PathBuf>, options: &calibrate::Options) -> ExitCode {
+    exit(
+        calibrate::calibrate(input.as_deref(), options).map(|summary| {
+            println!("{}", summary.sum);
+            if let Some(first) = summary.first_without_digits {
+                let action = match options.no_digits {
+                    day01::NoDigits::Zero => "counted as 0",
+                    _ => "skipped",
+                };
+                eprintln!(
+                    "{} of {} lines had no digits and were {}, the first is line {}",
+                    summary.without_digits,
+                    summary.decoded + summary.without_digits,
+                    action,
+                    first
+                );
+            }
+        }),
+    )
+}
@@ -0,0 +1,332 @@
+//! Results of `aoc run`, printed either as bare answers or as JSON.
+//!
+//! With `--format json` every solved part is printed as one JSON object on a
+//! line of its own (JSON Lines), so running several days produces several
+//! lines. The objects have these fields, and fields are only ever added, with
+//! `schema` bumped when an existing field changes meaning:
+//!
+//! | field         | type            | meaning                                  |
+//! |---------------|-----------------|------------------------------------------|
+//! | `schema`      | number          | version of this layout, currently `1`    |
+//! | `day`         | number          | day of the puzzle                        |
+//! | `part`        | number          | `1` or `2`                               |
+//! | `input`       | string          | path of the puzzle input                 |
+//! | `answer`      | string or null  | answer, `null` if the part failed        |
+//! | `parse_ns`    | number or null  | time spent parsing, in nanoseconds       |
+//! | `solve_ns`    | number or null  | time spent solving, in nanoseconds       |
+//! | `diagnostics` | array           | problems found, empty when all went well |
+//!
+//! Each diagnostic has `severity` (always `"error"` for now), a one-line
+//! `message`, the multi-line `rendered` form shown on the terminal, and
+//! `line`, `column`, `expected` and `found`, which are `null` unless the
+//! problem is a parse error.
+
+use crate::days;
+use common::{ParseError, Part, Solver};
+use serde::Serialize;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+pub const SCHEMA: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub schema: u32,
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub answer: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    pub severity: &'static str,
+    pub message: String,
+    pub rendered: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub expected: Option<String>,
+    pub found: Option<String>,
+}
+
+impl Diagnostic {
+    fn error(message: String) -> Diagnostic {
+        Diagnostic {
+            severity: "error",
+            rendered: message.clone(),
+            message,
+            line: None,
+            column: None,
+            expected: None,
+            found: None,
+        }
+    }
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(err: ParseError) -> Diagnostic {
+        Diagnostic {
+            severity: "error",
+            message: format!("expected {}, found {}", err.expected(), err.found()),
+            rendered: err.to_string(),
+            line: Some(err.line()),
+            column: Some(err.column()),
+            expected: Some(err.expected().to_string()),
+            found: Some(err.found().to_string()),
+        }
+    }
+}
+
+/// Solves the given parts of a day on the input at `path`, one record per
+/// part.
+pub fn solve(day: u32, parts: &[Part], path: &Path) -> Vec<Record> {
+    let record = |part: Part| Record {
+        schema: SCHEMA,
+        day,
+        part: part.number(),
+        input: path.display().to_string(),
+        answer: None,
+        parse_ns: None,
+        solve_ns: None,
+        diagnostics: Vec::new(),
+    };
+    let failed = |message: String| {
+        parts
+            .iter()
+            .map(|&part| Record {
+                diagnostics: vec![Diagnostic::error(message.clone())],
+                ..record(part)
+            })
+            .collect()
+    };
+
+    let Some(solver) = days::find(day) else {
+        return failed(format!("day {} is not solved yet", day));
+    };
+    let input = match days::read(path) {
+        Ok(input) => input,
+        Err(err) => return failed(err),
+    };
+
+    parts
+        .iter()
+        .map(|&part| run(solver, &input, path, part, record(part)))
+        .collect()
+}
+
+/// Fills in `record` with the outcome of one part. A panicking solver
+/// becomes a diagnostic as well, so every part still gets its record.
+fn run(solver: &dyn Solver, input: &str, path: &Path, part: Part, record: Record) -> Record {
+    match panic::catch_unwind(AssertUnwindSafe(|| solver.run(input, part))) {
+        Ok(Ok(run)) => Record {
+            answer: Some(run.answer),
+            parse_ns: Some(run.parse_time.as_nanos() as u64),
+            solve_ns: Some(run.solve_time.as_nanos() as u64),
+            ..record
+        },
+        Ok(Err(err)) => Record {
+            diagnostics: vec![err.in_file(path).into()],
+            ..record
+        },
+        Err(payload) => Record {
+            diagnostics: vec![Diagnostic::error(format!(
+                "solver panicked: {}",
+                panic_message(&*payload)
+            ))],
+            ..record
+        },
+    }
+}
+
+/// The message a panic was started with, if it has one.
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown cause"
+    }
+}
+
+pub fn print(record: &Record, format: Format) {
+    match format {
+        Format::Text => {
+            if let Some(answer) = &record.answer {
+                println!("{}", answer);
+            }
+            for diagnostic in &record.diagnostics {
+                eprintln!("aoc: {}", diagnostic.rendered);
+            }
+        }
+        Format::Json => match serde_json::to_string(record) {
+            Ok(json) => println!("{}", json),
+            Err(err) => eprintln!("aoc: cannot serialize result: {}", err),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{ParseError, Solution};
+    use serde_json::{json, Value};
+
+    const RECORD: [&str; 8] = [
+        "schema",
+        "day",
+        "part",
+        "input",
+        "answer",
+        "parse_ns",
+        "solve_ns",
+        "diagnostics",
+    ];
+    const DIAGNOSTIC: [&str; 7] = [
+        "severity", "message", "rendered", "line", "column", "expected", "found",
+    ];
+
+    struct Broken;
+
+    impl Solution for Broken {
+        type Input = ();
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Result<(), ParseError> {
+            match input.find('x') {
+                Some(i) => Err(ParseError::at(input, &input[i..i + 1], "a digit")),
+                None => Ok(()),
+            }
+        }
+
+        fn part1(_: &()) -> Result<u32, ParseError> {
+            Ok(1)
+        }
+
+        fn part2(_: &()) -> Result<u32, ParseError> {
+            panic!("out of cheese")
+        }
+    }
+
+    /// The record as JSON, after checking it has exactly the documented
+    /// fields.
+    fn json(record: Record) -> Value {
+        let value = serde_json::to_value(record).unwrap();
+        let keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        let mut expected = RECORD.to_vec();
+        expected.sort();
+        assert_eq!(keys, expected);
+        for diagnostic in value["diagnostics"].as_array().unwrap() {
+            let keys: Vec<&str> = diagnostic
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .collect();
+            let mut expected = DIAGNOSTIC.to_vec();
+            expected.sort();
+            assert_eq!(keys, expected);
+            assert_eq!(diagnostic["severity"], "error");
+        }
+        assert_eq!(value["schema"], 1);
+        value
+    }
+
+    fn record(part: Part) -> Record {
+        Record {
+            schema: SCHEMA,
+            day: 9,
+            part: part.number(),
+            input: "in.txt".to_string(),
+            answer: None,
+            parse_ns: None,
+            solve_ns: None,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn solved_part() {
+        let value = json(run(
+            &Broken,
+            "1 2",
+            Path::new("in.txt"),
+            Part::One,
+            record(Part::One),
+        ));
+        assert_eq!(value["day"], 9);
+        assert_eq!(value["part"], 1);
+        assert_eq!(value["input"], "in.txt");
+        assert_eq!(value["answer"], "1");
+        assert!(value["parse_ns"].is_u64());
+        assert!(value["solve_ns"].is_u64());
+        assert_eq!(value["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn parse_error() {
+        let value = json(run(
+            &Broken,
+            "1\n2x",
+            Path::new("in.txt"),
+            Part::One,
+            record(Part::One),
+        ));
+        assert_eq!(value["answer"], Value::Null);
+        assert_eq!(value["parse_ns"], Value::Null);
+        assert_eq!(value["solve_ns"], Value::Null);
+        let diagnostic = &value["diagnostics"][0];
+        assert_eq!(diagnostic["message"], "expected a digit, found `x`");
+        assert_eq!(diagnostic["line"], 2);
+        assert_eq!(diagnostic["column"], 2);
+        assert_eq!(diagnostic["expected"], "a digit");
+        assert_eq!(diagnostic["found"], "`x`");
+        assert!(diagnostic["rendered"]
+            .as_str()
+            .unwrap()
+            .contains("in.txt:2:2"));
+    }
+
+    #[test]
+    fn panicking_solver() {
+        let value = json(run(
+            &Broken,
+            "1",
+            Path::new("in.txt"),
+            Part::Two,
+            record(Part::Two),
+        ));
+        assert_eq!(value["answer"], Value::Null);
+        let diagnostic = &value["diagnostics"][0];
+        assert_eq!(diagnostic["message"], "solver panicked: out of cheese");
+        assert_eq!(diagnostic["line"], Value::Null);
+    }
+
+    #[test]
+    fn unreadable_input() {
+        let records = solve(1, &Part::ALL, Path::new("/nonexistent/input.txt"));
+        assert_eq!(records.len(), 2);
+        for record in records {
+            let value = json(record);
+            assert_eq!(value["answer"], Value::Null);
+            let message = value["diagnostics"][0]["message"].as_str().unwrap();
+            assert!(message.starts_with("cannot read /nonexistent/input.txt"));
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::days;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("templates/Cargo.toml.in");
+const LIB_RS: &str = include_str!("templates/lib.rs.in");
+const MAIN_RS: &str = include_str!("templates/main.rs.in");
+
+/// Creates `dayNN/` from the templates and registers it with the runner.
+/// Returns the files that were written.
+pub fn new_day(day: u32) -> Result<Vec<PathBuf>, String> {
+    let name = format!("day{:02}", day);
+    let type_name = format!("Day{:02}", day);
+    let directory = days::directory(day);
+    if directory.join("lib.rs").exists() {
+        return Err(format!("{} already exists", directory.display()));
+    }
+    fs::create_dir_all(&directory)
+        .map_err(|err| format!("cannot create {}: {}", directory.display(), err))?;
+
+    let files = [
+        ("Cargo.toml", CARGO_TOML),
+        ("lib.rs", LIB_RS),
+        ("main.rs", MAIN_RS),
+        ("example.txt", ""),
+        ("input.txt", ""),
+    ];
+    let mut written = Vec::new();
+    for (file, template) in files {
+        let path = directory.join(file);
+        if path.exists() {
+            // Most likely an input that was fetched before the day was created.
+            continue;
+        }
+        let contents = template
+            .replace("%day%", &name)
+            .replace("%Day%", &type_name);
+        fs::write(&path, contents)
+            .map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+        written.push(path);
+    }
+
+    let runner = days::workspace().join("aoc");
+    let dependency = format!("{} = {{ path = \"../{}\" }}", name, name);
+    let entry = format!("    ({}, &{}::{}),", day, name, type_name);
+    register(&runner.join("Cargo.toml"), day, &dependency, |line| {
+        line.starts_with("day")
+    })?;
+    register(&runner.join("days.rs"), day, &entry, |line| {
+        line.starts_with("    (")
+    })?;
+    written.push(runner.join("Cargo.toml"));
+    written.push(runner.join("days.rs"));
+
+    let answers = days::workspace().join("answers.toml");
+    if add_example(&answers, day)? {
+        written.push(answers);
+    }
+    Ok(written)
+}
+
+/// Adds `new_line` to the run of lines in `path` that mention a day, keeping
+/// the run sorted by day.
+fn register(
+    path: &Path,
+    day: u32,
+    new_line: &str,
+    is_entry: impl Fn(&str) -> bool,
+) -> Result<(), String> {
+    let text = days::read(path)?;
+    let mut lines: Vec<_> = text.lines().collect();
+
+    let entries: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_entry(line))
+        .filter_map(|(i, line)| day_of(line).map(|day| (i, day)))
+        .collect();
+    if entries.iter().any(|&(_, existing)| existing == day) {
+        return Ok(());
+    }
+    let position = match entries.iter().rev().find(|&&(_, existing)| existing < day) {
+        Some(&(i, _)) => i + 1,
+        None => match entries.first() {
+            Some(&(i, _)) => i,
+            None => return Err(format!("no day is registered in {}", path.display())),
+        },
+    };
+    lines.insert(position, new_line);
+
+    fs::write(path, lines.join("\n") + "\n")
+        .map_err(|err| format!("cannot write {}: {}", path.display(), err))
+}
+
+/// Adds an empty `[dayNN.example]` table to the answers file, so `aoc
+/// verify` lists the example answers as missing until they are filled in.
+/// Returns whether the file changed.
+fn add_example(path: &Path, day: u32) -> Result<bool, String> {
+    let text = days::read(path)?;
+    let header = format!("[day{:02}.example]", day);
+    if text.lines().any(|line| line.trim() == header) {
+        return Ok(false);
+    }
+    let table = format!(
+        "\n{}\n# Answers given in the puzzle text for example.txt.\n# part1 = \"\"\n# part2 = \"\"\n",
+        header
+    );
+    fs::write(path, text + &table)
+        .map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+    Ok(true)
+}
+
+/// Day number of the first `dayNN` mentioned in `line`.
+fn day_of(line: &str) -> Option<u32> {
+    let start = line.find("day")? + "day".len();
+    line.get(start..start + 2)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Answers;
+    use crate::stub;
+    use common::Part;
+
+    const CARGO_TOML: &str = "\
+[dependencies]
+common = { path = \"../common\" }
+day01 = { path = \"../day01\" }
+day03 = { path = \"../day03\" }
+serde = \"1\"
+";
+
+    const DAYS_RS: &str = "\
+pub static DAYS: &[(u32, &dyn Solver)] = &[
+    (2, &day02::Day02),
+    (3, &day03::Day03),
+];
+";
+
+    fn register_in(text: &str, day: u32, new_line: &str, prefix: &str) -> Result<String, String> {
+        let dir = stub::state_dir(&format!("register-{}", day));
+        let path = dir.path().join("file");
+        fs::write(&path, text).unwrap();
+        register(&path, day, new_line, |line| line.starts_with(prefix))?;
+        Ok(fs::read_to_string(&path).unwrap())
+    }
+
+    #[test]
+    fn registers_dependencies_in_day_order() {
+        let line = "day02 = { path = \"../day02\" }";
+        let text = register_in(CARGO_TOML, 2, line, "day").unwrap();
+        assert_eq!(
+            text,
+            CARGO_TOML.replace("day03 = ", &format!("{}\nday03 = ", line))
+        );
+
+        let line = "day04 = { path = \"../day04\" }";
+        let text = register_in(CARGO_TOML, 4, line, "day").unwrap();
+        assert_eq!(
+            text,
+            CARGO_TOML.replace("serde", &format!("{}\nserde", line))
+        );
+    }
+
+    #[test]
+    fn registers_solvers_in_day_order() {
+        let line = "    (1, &day01::Day01),";
+        let text = register_in(DAYS_RS, 1, line, "    (").unwrap();
+        assert_eq!(
+            text,
+            DAYS_RS.replace("    (2", &format!("{}\n    (2", line))
+        );
+
+        let line = "    (7, &day07::Day07),";
+        let text = register_in(DAYS_RS, 7, line, "    (").unwrap();
+        assert_eq!(text, DAYS_RS.replace("];", &format!("{}\n];", line)));
+    }
+
+    #[test]
+    fn registering_twice_changes_nothing() {
+        let text = register_in(DAYS_RS, 3, "    (3, &day03::Day03),", "    (").unwrap();
+        assert_eq!(text, DAYS_RS);
+    }
+
+    #[test]
+    fn registering_needs_an_existing_day() {
+        let err = register_in("[dependencies]\n", 1, "day01 = {}", "day").unwrap_err();
+        assert!(err.starts_with("no day is registered in "), "{}", err);
+    }
+
+    #[test]
+    fn adds_the_example_table_once() {
+        let dir = stub::state_dir("add-example");
+        let path = dir.path().join("answers.toml");
+        fs::write(&path, "[day01.example]\npart1 = \"142\"\n").unwrap();
+
+        assert!(add_example(&path, 7).unwrap());
+        assert!(!add_example(&path, 7).unwrap());
+        assert!(!add_example(&path, 1).unwrap());
+
+        let answers = Answers::load(&path).unwrap();
+        assert_eq!(answers.inputs(7).collect::<Vec<_>>(), ["example"]);
+        assert_eq!(answers.get(7, "example", Part::One), None);
+        assert_eq!(answers.get(1, "example", Part::One), Some("142"));
+    }
+}
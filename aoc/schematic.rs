@@ -0,0 +1,108 @@
+use common::{Grid, Solution};
+use day03::{Blanks, Day03, GearRule, PartNumber};
+use std::path::Path;
+
+use crate::days;
+
+/// Which numbers of a schematic to list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Filter {
+    All,
+    Parts,
+    NotParts,
+}
+
+/// Lists the numbers of a day 3 schematic with the symbols touching them,
+/// using 1-based `line:column` positions. Characters in `blanks` are not
+/// symbols.
+pub fn numbers(input: Option<&Path>, filter: Filter, blanks: &str) -> Result<(), String> {
+    let (_, numbers) = load(input, &Blanks::new(blanks.chars()))?;
+
+    for number in numbers {
+        let listed = match filter {
+            Filter::All => true,
+            Filter::Parts => number.is_part(),
+            Filter::NotParts => !number.is_part(),
+        };
+        if !listed {
+            continue;
+        }
+        let symbols: Vec<_> = number
+            .adjacent_symbols
+            .iter()
+            .map(|&((row, col), symbol)| format!("{} at {}:{}", symbol, row + 1, col + 1))
+            .collect();
+        let position = format!("{}:{}", number.row + 1, number.col_range.start + 1);
+        let symbols = if symbols.is_empty() {
+            "no symbols".to_string()
+        } else {
+            symbols.join(", ")
+        };
+        println!("{:<9} {:>6}  {}", position, number.value, symbols);
+    }
+    Ok(())
+}
+
+/// Prints what every symbol character of a day 3 schematic touches.
+pub fn symbols(input: Option<&Path>, blanks: &str) -> Result<(), String> {
+    let blanks = Blanks::new(blanks.chars());
+    let (grid, numbers) = load(input, &blanks)?;
+    let symbols = day03::find_symbols(&grid, &blanks, &numbers);
+
+    println!("symbol  count  part sum  numbers touched: symbols");
+    for (symbol, stats) in day03::symbol_stats(&numbers, &symbols) {
+        let by_adjacent: Vec<_> = stats
+            .by_adjacent
+            .iter()
+            .map(|(numbers, symbols)| format!("{}:{}", numbers, symbols))
+            .collect();
+        println!(
+            "{:<6} {:>6} {:>9}  {}",
+            symbol,
+            stats.count,
+            stats.part_sum,
+            by_adjacent.join(" ")
+        );
+    }
+    Ok(())
+}
+
+/// Lists the gears of a day 3 schematic under `rule` with their ratios,
+/// followed by the sum of the ratios.
+pub fn gears(input: Option<&Path>, rule: &GearRule, blanks: &str) -> Result<(), String> {
+    let blanks = Blanks::new(blanks.chars());
+    let (grid, numbers) = load(input, &blanks)?;
+    let symbols = day03::find_symbols(&grid, &blanks, &numbers);
+
+    let gears = day03::find_gears(&symbols, rule).map_err(|gear| {
+        format!(
+            "the ratio of the {} at {}:{} does not fit in 64 bits",
+            gear.symbol,
+            gear.cell.0 + 1,
+            gear.cell.1 + 1
+        )
+    })?;
+    for (gear, ratio) in &gears {
+        let numbers: Vec<_> = gear.numbers.iter().map(u32::to_string).collect();
+        let position = format!("{}:{}", gear.cell.0 + 1, gear.cell.1 + 1);
+        println!(
+            "{:<9} {}  {:<20} {:>10}",
+            position,
+            gear.symbol,
+            numbers.join(", "),
+            ratio
+        );
+    }
+    let total: u64 = gears.iter().map(|(_, ratio)| ratio).sum();
+    println!("{} gears, ratios sum to {}", gears.len(), total);
+    Ok(())
+}
+
+/// Reads a day 3 schematic and finds its numbers.
+fn load(input: Option<&Path>, blanks: &Blanks) -> Result<(Grid<char>, Vec<PartNumber>), String> {
+    let path = days::input_path(3, input);
+    let grid = Day03::parse(&days::read(&path)?).map_err(|err| err.in_file(&path).to_string())?;
+    let numbers =
+        day03::extract_numbers(&grid, blanks).map_err(|err| err.in_file(&path).to_string())?;
+    Ok((grid, numbers))
+}
@@ -0,0 +1,360 @@
+use crate::client::Client;
+use crate::days;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What the website said about a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the previous answer; seconds left to wait.
+    Wait(u64),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "not checked, wait {}s", seconds),
+            Verdict::AlreadySolved => write!(f, "not checked, part already solved"),
+            Verdict::Unknown(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Unix time of the submission.
+    pub at: u64,
+    /// Unix time before which the website will not accept another answer.
+    pub retry_at: Option<u64>,
+}
+
+/// Every submission made from this checkout, kept in `submissions.toml` in
+/// the state directory.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default, rename = "submission")]
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History, String> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+        let text = days::read(path)?;
+        toml::from_str(&text).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string(self).map_err(|err| err.to_string())?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| format!("cannot create {}: {}", parent.display(), err))?;
+        }
+        fs::write(path, text).map_err(|err| format!("cannot write {}: {}", path.display(), err))
+    }
+
+    /// Why `answer` should not be sent, judging by earlier submissions.
+    pub fn refusal(&self, year: u32, day: u32, part: u32, answer: &str) -> Option<String> {
+        let now = unix_now();
+        if let Some(retry_at) = self.submissions.iter().filter_map(|s| s.retry_at).max() {
+            if retry_at > now {
+                return Some(format!("wait {}s before submitting again", retry_at - now));
+            }
+        }
+
+        let earlier = self
+            .submissions
+            .iter()
+            .filter(|s| s.year == year && s.day == day && s.part == part);
+        let value: Option<i128> = answer.parse().ok();
+        for submission in earlier {
+            let numbers = value.zip(submission.answer.parse::<i128>().ok());
+            match &submission.verdict {
+                Verdict::Correct => {
+                    return Some(format!("already solved with {}", submission.answer));
+                }
+                Verdict::AlreadySolved => return Some("part already solved".to_string()),
+                Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong
+                    if submission.answer == answer =>
+                {
+                    return Some(format!("{} was already {}", answer, submission.verdict));
+                }
+                Verdict::TooHigh if numbers.is_some_and(|(value, known)| value >= known) => {
+                    return Some(format!("{} was already too high", submission.answer));
+                }
+                Verdict::TooLow if numbers.is_some_and(|(value, known)| value <= known) => {
+                    return Some(format!("{} was already too low", submission.answer));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+}
+
+/// Posts `answer` for the given part unless the history already tells how it
+/// would go, and records what the website answered.
+pub fn submit(client: &Client, day: u32, part: u32, answer: &str) -> Result<Verdict, String> {
+    let config = client.config();
+    let path = config.state_dir.join("submissions.toml");
+    let mut history = History::load(&path)?;
+    if let Some(reason) = history.refusal(config.year, day, part, answer) {
+        return Err(format!("not submitting: {}", reason));
+    }
+
+    let level = part.to_string();
+    let response = client.post_form(
+        &format!("/{}/day/{}/answer", config.year, day),
+        &[("level", &level), ("answer", answer)],
+    )?;
+    let text = article_text(&response);
+    let verdict = parse_verdict(&text);
+
+    let at = unix_now();
+    let retry_at = match verdict {
+        Verdict::Wait(seconds) => Some(at + seconds),
+        _ => parse_cooldown(&text).map(|seconds| at + seconds),
+    };
+    history.record(Submission {
+        year: config.year,
+        day,
+        part,
+        answer: answer.to_string(),
+        verdict: verdict.clone(),
+        at,
+        retry_at,
+    });
+    history.save(&path)?;
+    Ok(verdict)
+}
+
+pub fn parse_verdict(text: &str) -> Verdict {
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait(parse_time_left(text).unwrap_or(60))
+    } else if text.contains("Did you already complete it") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown(text.to_string())
+    }
+}
+
+/// Parses "You have 1m 23s left to wait".
+fn parse_time_left(text: &str) -> Option<u64> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    text[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+/// Parses the "please wait 5 minutes before trying again" that follows a
+/// wrong answer.
+fn parse_cooldown(text: &str) -> Option<u64> {
+    let lower = text.to_lowercase();
+    let start = lower.find("please wait ")? + "please wait ".len();
+    let mut words = lower[start..].split_whitespace();
+    let minutes = match words.next()? {
+        "one" => 1,
+        number => number.parse().ok()?,
+    };
+    words
+        .next()
+        .filter(|unit| unit.starts_with("minute"))
+        .map(|_| minutes * 60)
+}
+
+/// Text of the `<article>` element holding the website's message, without
+/// markup.
+fn article_text(html: &str) -> String {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |i| start + i);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html[start..end].chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{self, Server};
+
+    const TOO_HIGH: &str = r#"<main>
+<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href="/2023/about">about page</a>, or you can ask for hints on the <a href="https://www.reddit.com/r/adventofcode/" target="_blank">subreddit</a>.  Please wait one minute before trying again. <a href="/2023/day/1">[Return to Day 1]</a></p></article>
+</main>"#;
+    const TOO_LOW: &str = "That's not the right answer; your answer is too low. If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. [Return to Day 1]";
+    const WRONG: &str = "That's not the right answer. If you're stuck, make sure you're using the full input data. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again. [Return to Day 1]";
+    const TOO_SOON: &str = "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 23s left to wait. [Return to Day 1]";
+    const SOLVED: &str = "You don't seem to be solving the right level. Did you already complete it? [Return to Day 1]";
+    const CORRECT: &str = "That's the right answer! You are one gold star closer to restoring snow operations. [Continue to Part Two]";
+
+    #[test]
+    fn verdicts() {
+        assert_eq!(parse_verdict(&article_text(TOO_HIGH)), Verdict::TooHigh);
+        assert_eq!(parse_verdict(TOO_LOW), Verdict::TooLow);
+        assert_eq!(parse_verdict(WRONG), Verdict::Wrong);
+        assert_eq!(parse_verdict(TOO_SOON), Verdict::Wait(83));
+        assert_eq!(parse_verdict(SOLVED), Verdict::AlreadySolved);
+        assert_eq!(parse_verdict(CORRECT), Verdict::Correct);
+        assert!(matches!(
+            parse_verdict("<html></html>"),
+            Verdict::Unknown(_)
+        ));
+    }
+
+    #[test]
+    fn article_text_drops_markup() {
+        assert!(article_text(TOO_HIGH).starts_with("That's not the right answer; your"));
+        assert!(article_text(TOO_HIGH).ends_with("before trying again. [Return to Day 1]"));
+    }
+
+    #[test]
+    fn time_left() {
+        assert_eq!(parse_time_left(TOO_SOON), Some(83));
+        assert_eq!(parse_time_left("You have 45s left to wait."), Some(45));
+        assert_eq!(
+            parse_time_left("You have 1h 2m 3s left to wait."),
+            Some(3723)
+        );
+        assert_eq!(parse_time_left("You have a while left to wait."), None);
+    }
+
+    #[test]
+    fn cooldowns() {
+        assert_eq!(parse_cooldown(&article_text(TOO_HIGH)), Some(60));
+        assert_eq!(parse_cooldown(WRONG), Some(300));
+        assert_eq!(parse_cooldown(CORRECT), None);
+    }
+
+    fn history(submissions: &[(&str, Verdict)]) -> History {
+        History {
+            submissions: submissions
+                .iter()
+                .map(|(answer, verdict)| Submission {
+                    year: 2023,
+                    day: 1,
+                    part: 1,
+                    answer: answer.to_string(),
+                    verdict: verdict.clone(),
+                    at: 0,
+                    retry_at: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn refuses_a_known_wrong_answer() {
+        let history = history(&[("42", Verdict::Wrong)]);
+        assert!(history.refusal(2023, 1, 1, "42").is_some());
+        assert!(history.refusal(2023, 1, 1, "43").is_none());
+        assert!(history.refusal(2023, 1, 2, "42").is_none());
+        assert!(history.refusal(2023, 2, 1, "42").is_none());
+    }
+
+    #[test]
+    fn refuses_answers_beyond_known_bounds() {
+        let history = history(&[("100", Verdict::TooHigh), ("10", Verdict::TooLow)]);
+        assert!(history.refusal(2023, 1, 1, "100").is_some());
+        assert!(history.refusal(2023, 1, 1, "150").is_some());
+        assert!(history.refusal(2023, 1, 1, "10").is_some());
+        assert!(history.refusal(2023, 1, 1, "5").is_some());
+        assert!(history.refusal(2023, 1, 1, "50").is_none());
+        assert!(history.refusal(2023, 1, 1, "fifty").is_none());
+    }
+
+    #[test]
+    fn refuses_solved_parts_and_waits_for_cooldowns() {
+        let solved = history(&[("55", Verdict::Correct)]);
+        assert!(solved.refusal(2023, 1, 1, "56").is_some());
+
+        let mut waiting = history(&[]);
+        waiting.record(Submission {
+            retry_at: Some(unix_now() + 60),
+            ..history(&[("1", Verdict::Wrong)]).submissions.remove(0)
+        });
+        assert!(waiting.refusal(2023, 5, 2, "7").is_some());
+    }
+
+    #[test]
+    fn submits_to_the_website_and_records_the_verdict() {
+        let server = Server::start(|_, _| (200, TOO_HIGH.to_string()));
+        let state_dir = stub::state_dir("submit");
+        let config = stub::config(&server, &state_dir);
+        let client = Client::new(&config);
+
+        assert_eq!(submit(&client, 1, 1, "100"), Ok(Verdict::TooHigh));
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2023/day/1/answer");
+        assert_eq!(requests[0].body, "level=1&answer=100");
+        assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+
+        let history = History::load(&config.state_dir.join("submissions.toml")).unwrap();
+        assert_eq!(history.submissions.len(), 1);
+        assert!(history.submissions[0].retry_at.is_some());
+        // The cooldown and the known bound both stop a second submission
+        // before it reaches the server.
+        assert!(submit(&client, 1, 1, "120").is_err());
+        assert_eq!(server.requests().len(), 1);
+    }
+}
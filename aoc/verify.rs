@@ -0,0 +1,102 @@
+use crate::answers::Answers;
+use crate::days;
+use crate::output;
+use common::{Part, Solver};
+use std::collections::BTreeSet;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+
+enum Outcome {
+    Pass(String),
+    Fail { expected: String, actual: String },
+    Error(String),
+    Missing,
+}
+
+/// Runs every part of every input of the given days and compares the
+/// results with the recorded answers. Returns whether nothing failed.
+pub fn verify(day_numbers: &[u32], answers: &Answers) -> bool {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    for &day in day_numbers {
+        let Some(solver) = days::find(day) else {
+            println!("day{:02}  not solved yet", day);
+            missing += 1;
+            continue;
+        };
+        for input in input_names(day, answers) {
+            for part in Part::ALL {
+                let outcome = check(day, solver, &input, part, answers);
+                print!("day{:02}  {:<10} part {}  ", day, input, part);
+                match outcome {
+                    Outcome::Pass(answer) => {
+                        passed += 1;
+                        println!("ok       {}", answer);
+                    }
+                    Outcome::Fail { expected, actual } => {
+                        failed += 1;
+                        println!("FAIL");
+                        println!("    - {}", expected);
+                        println!("    + {}", actual);
+                    }
+                    Outcome::Error(message) => {
+                        failed += 1;
+                        println!("ERROR");
+                        for line in message.lines() {
+                            println!("    {}", line);
+                        }
+                    }
+                    Outcome::Missing => {
+                        missing += 1;
+                        println!("missing");
+                    }
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    failed == 0
+}
+
+fn check(day: u32, solver: &dyn Solver, input: &str, part: Part, answers: &Answers) -> Outcome {
+    let Some(expected) = answers.get(day, input, part) else {
+        return Outcome::Missing;
+    };
+    let path = days::directory(day).join(format!("{}.txt", input));
+    let text = match days::read(&path) {
+        Ok(text) => text,
+        Err(err) => return Outcome::Error(err),
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| solver.run(&text, part))) {
+        Ok(Ok(run)) if run.answer == expected => Outcome::Pass(run.answer),
+        Ok(Ok(run)) => Outcome::Fail {
+            expected: expected.to_string(),
+            actual: run.answer,
+        },
+        Ok(Err(err)) => Outcome::Error(err.in_file(path).to_string()),
+        Err(payload) => Outcome::Error(format!(
+            "solver panicked: {}",
+            output::panic_message(&*payload)
+        )),
+    }
+}
+
+/// Inputs of a day: every `.txt` file in its directory plus any input that
+/// only appears in the answers file.
+fn input_names(day: u32, answers: &Answers) -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = answers.inputs(day).map(String::from).collect();
+    if let Ok(entries) = fs::read_dir(days::directory(day)) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                if let Some(stem) = path.file_stem() {
+                    names.insert(stem.to_string_lossy().into_owned());
+                }
+            }
+        }
+    }
+    names
+}
@@ -0,0 +1,284 @@
+use std::fmt;
+use std::ops::{Index, IndexMut, Range};
+
+use crate::ParseError;
+
+const OFFSETS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid stored row by row. Cells are addressed as `(row, col)`
+/// and every accessor only ever yields coordinates inside the grid.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Builds a grid from its rows, or returns the index of the first row
+    /// whose length differs from the first one.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, usize> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some(bad) = rows.iter().position(|row| row.len() != cols) {
+            return Err(bad);
+        }
+        Ok(Grid {
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.rows && col < self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.contains(row, col)
+            .then(|| &self.cells[row * self.cols + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if self.contains(row, col) {
+            Some(&mut self.cells[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    /// Up, left, right and down neighbours of a cell that lie inside the grid.
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_cells(row, col, &OFFSETS4)
+    }
+
+    /// All eight neighbours of a cell, diagonals included, that lie inside
+    /// the grid.
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_cells(row, col, &OFFSETS8)
+    }
+
+    fn offset_cells<'a>(
+        &'a self,
+        row: usize,
+        col: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let row = row.checked_add_signed(dr)?;
+            let col = col.checked_add_signed(dc)?;
+            self.contains(row, col).then_some((row, col))
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} out of bounds", row);
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.cols, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    /// Every cell with its coordinates, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i / self.cols, i % self.cols), cell))
+    }
+
+    /// Cells of the rectangle spanned by `rows` and `cols`, clipped to the
+    /// grid, row by row.
+    pub fn region(
+        &self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let rows = rows.start.min(self.rows)..rows.end.min(self.rows);
+        let cols = cols.start.min(self.cols)..cols.end.min(self.cols);
+        rows.flat_map(move |row| {
+            cols.clone()
+                .map(move |col| ((row, col), &self.cells[row * self.cols + col]))
+        })
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: self.iter_columns().flatten().cloned().collect(),
+            rows: self.cols,
+            cols: self.rows,
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parses one row per line, requiring all lines to be equally long.
+    pub fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        let lines: Vec<_> = input.lines().collect();
+        Grid::from_rows(lines.iter().map(|line| line.chars().collect()).collect()).map_err(|bad| {
+            let cols = lines[0].chars().count();
+            ParseError::at(input, lines[bad], format!("a line of {} characters", cols))
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("cell ({}, {}) out of bounds", row, col))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("cell ({}, {}) out of bounds", row, col))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\nghi\n").unwrap()
+    }
+
+    fn sorted(cells: impl Iterator<Item = (usize, usize)>) -> Vec<(usize, usize)> {
+        let mut cells: Vec<_> = cells.collect();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn neighbours_at_corners_and_edges() {
+        let grid = grid();
+        assert_eq!(sorted(grid.neighbours4(0, 0)), [(0, 1), (1, 0)]);
+        assert_eq!(sorted(grid.neighbours4(2, 2)), [(1, 2), (2, 1)]);
+        assert_eq!(sorted(grid.neighbours4(0, 1)), [(0, 0), (0, 2), (1, 1)]);
+        assert_eq!(sorted(grid.neighbours4(1, 1)).len(), 4);
+
+        assert_eq!(sorted(grid.neighbours8(0, 0)), [(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(sorted(grid.neighbours8(2, 0)), [(1, 0), (1, 1), (2, 1)]);
+        assert_eq!(
+            sorted(grid.neighbours8(1, 2)),
+            [(0, 1), (0, 2), (1, 1), (2, 1), (2, 2)]
+        );
+        assert_eq!(sorted(grid.neighbours8(1, 1)).len(), 8);
+    }
+
+    #[test]
+    fn region_is_clipped_to_the_grid() {
+        let grid = grid();
+        let cells: String = grid.region(1..5, 2..9).map(|(_, &c)| c).collect();
+        assert_eq!(cells, "fi");
+        let cells: Vec<_> = grid.region(0..2, 0..2).map(|(at, _)| at).collect();
+        assert_eq!(cells, [(0, 0), (0, 1), (1, 0), (1, 1)]);
+        assert_eq!(grid.region(3..5, 0..3).count(), 0);
+    }
+
+    #[test]
+    fn rows_columns_and_transpose() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.rows(), transposed.cols()), (3, 2));
+        assert_eq!(
+            transposed.iter_rows().collect::<Vec<_>>(),
+            [[1, 4], [2, 5], [3, 6]]
+        );
+        assert_eq!(transposed.transpose(), grid);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 3 out of bounds")]
+    fn row_out_of_bounds() {
+        grid().row(3);
+    }
+
+    #[test]
+    fn display_prints_one_line_per_row() {
+        assert_eq!(grid().to_string(), "abc\ndef\nghi\n");
+        let numbers = Grid::new(2, 3, 7);
+        assert_eq!(numbers.to_string(), "777\n777\n");
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3], vec![4, 5]]),
+            Err(1)
+        );
+        assert_eq!(Grid::<u8>::from_rows(Vec::new()).map(|g| g.rows()), Ok(0));
+
+        let err = Grid::parse("abc\ndef\ngh\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (3, 1));
+        assert_eq!(err.expected(), "a line of 3 characters");
+        assert_eq!(err.found(), "`gh`");
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::process;
+use std::time::Duration;
+
+mod grid;
+mod measure;
+mod parse;
+
+pub use grid::Grid;
+pub use measure::{measure, CountingAllocator, Sample, Stage};
+pub use parse::{parse_lines, parse_number, ParseError};
+
+/// A day's puzzle, split into the stages every solution goes through: the
+/// input is parsed once and both parts are computed from the parsed value.
+/// A part fails when the input parses but does not make sense for that part,
+/// such as a line that only the other part knows how to read.
+pub trait Solution {
+    type Input;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, ParseError>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, ParseError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: u32) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Answer of a single part together with how long each stage took.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Object-safe view of a [`Solution`], so days can be stored side by side.
+pub trait Solver: Sync {
+    fn run(&self, input: &str, part: Part) -> Result<Run, ParseError>;
+
+    /// Runs the parse stage and then each of the given parts `runs` times,
+    /// returning one sample per run for every stage.
+    fn bench(
+        &self,
+        input: &str,
+        parts: &[Part],
+        runs: usize,
+    ) -> Result<Vec<(Stage, Vec<Sample>)>, ParseError>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn run(&self, input: &str, part: Part) -> Result<Run, ParseError> {
+        let (parsed, parse) = measure(|| S::parse(input));
+        let parsed = parsed?;
+        let (answer, solve) = measure(|| solve::<S>(&parsed, part));
+
+        Ok(Run {
+            answer: answer?,
+            parse_time: parse.time,
+            solve_time: solve.time,
+        })
+    }
+
+    fn bench(
+        &self,
+        input: &str,
+        parts: &[Part],
+        runs: usize,
+    ) -> Result<Vec<(Stage, Vec<Sample>)>, ParseError> {
+        let parsed = S::parse(input)?;
+        for &part in parts {
+            solve::<S>(&parsed, part)?;
+        }
+        let mut stages = Vec::new();
+
+        let samples = (0..runs)
+            .map(|_| measure(|| black_box(S::parse(black_box(input)))).1)
+            .collect();
+        stages.push((Stage::Parse, samples));
+
+        for &part in parts {
+            let samples = (0..runs)
+                .map(|_| measure(|| black_box(solve::<S>(black_box(&parsed), part))).1)
+                .collect();
+            stages.push((Stage::Solve(part), samples));
+        }
+        Ok(stages)
+    }
+}
+
+fn solve<S: Solution>(parsed: &S::Input, part: Part) -> Result<String, ParseError> {
+    match part {
+        Part::One => S::part1(parsed).map(|answer| answer.to_string()),
+        Part::Two => S::part2(parsed).map(|answer| answer.to_string()),
+    }
+}
+
+/// Entry point of the per-day binaries: solves both parts of `input.txt` in
+/// the current directory.
+pub fn run_day<S: Solution>() {
+    let input = match fs::read_to_string("input.txt") {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: cannot read input.txt: {}", err);
+            process::exit(1);
+        }
+    };
+    let parsed = S::parse(&input).unwrap_or_else(|err| fail(err));
+    for part in Part::ALL {
+        match solve::<S>(&parsed, part) {
+            Ok(answer) => println!("{}", answer),
+            Err(err) => fail(err),
+        }
+    }
+}
+
+fn fail(err: ParseError) -> ! {
+    eprintln!("error: {}", err.in_file("input.txt"));
+    process::exit(1);
+}
@@ -0,0 +1,70 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::Part;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// System allocator that counts allocations. Binaries install it with
+/// `#[global_allocator]`; without it [`Sample::allocations`] is always zero.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// A stage of a [`crate::Solution`] that can be timed on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+/// Cost of a single run of a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub time: Duration,
+    /// Allocations made by the whole process while the stage ran.
+    pub allocations: u64,
+}
+
+/// Runs `f` once, measuring how long it takes and how much it allocates.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Sample) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let value = f();
+    let time = start.elapsed();
+    let sample = Sample {
+        time,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+    };
+    (value, sample)
+}
@@ -0,0 +1,251 @@
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Malformed puzzle input, pointing at the offending part of the text.
+///
+/// Errors are created relative to the text a parser was given and moved
+/// outwards with [`ParseError::within`], so a parser working on a single line
+/// does not need to know where that line sits in the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError(Box<Details>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Details {
+    file: Option<PathBuf>,
+    line: usize,
+    column: usize,
+    expected: String,
+    found: String,
+    source_line: String,
+    width: usize,
+    offset: usize,
+    len: usize,
+}
+
+impl ParseError {
+    /// Error about `span`, which must be a slice of `source`.
+    pub fn at(source: &str, span: &str, expected: impl Into<String>) -> ParseError {
+        let offset = offset_of(source, span);
+        let mut error = ParseError(Box::new(Details {
+            file: None,
+            line: 0,
+            column: 0,
+            expected: expected.into(),
+            found: String::new(),
+            source_line: String::new(),
+            width: 0,
+            offset,
+            len: span.len(),
+        }));
+        error.0.locate(source);
+        error
+    }
+
+    /// Error at the very end of `source`, for input that stops too early.
+    pub fn at_end(source: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::at(source, &source[source.len()..], expected)
+    }
+
+    /// Moves an error raised while parsing `part` into `source`, which
+    /// contains `part`.
+    pub fn within(mut self, source: &str, part: &str) -> ParseError {
+        self.0.offset += offset_of(source, part);
+        self.0.locate(source);
+        self
+    }
+
+    /// Moves an error down by `lines` lines, for errors in a chunk of a larger
+    /// input that is not kept in memory as a whole.
+    pub fn after_lines(mut self, lines: usize) -> ParseError {
+        self.0.line += lines;
+        self
+    }
+
+    pub fn in_file(mut self, file: impl Into<PathBuf>) -> ParseError {
+        self.0.file = Some(file.into());
+        self
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.0.file.as_deref()
+    }
+
+    /// 1-based line number.
+    pub fn line(&self) -> usize {
+        self.0.line
+    }
+
+    /// 1-based column, counted in characters.
+    pub fn column(&self) -> usize {
+        self.0.column
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.0.expected
+    }
+
+    pub fn found(&self) -> &str {
+        &self.0.found
+    }
+}
+
+impl Details {
+    fn locate(&mut self, source: &str) {
+        let line_start = source[..self.offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[self.offset..]
+            .find('\n')
+            .map_or(source.len(), |i| self.offset + i);
+        let span = source[self.offset..self.offset + self.len]
+            .lines()
+            .next()
+            .unwrap_or_default();
+
+        self.line = source[..line_start].matches('\n').count() + 1;
+        self.column = source[line_start..self.offset].chars().count() + 1;
+        self.source_line = source[line_start..line_end]
+            .trim_end_matches('\r')
+            .to_string();
+        self.width = span.chars().count().max(1);
+        self.found = if !span.is_empty() {
+            format!("`{}`", span)
+        } else {
+            match source[self.offset..].chars().next() {
+                None => "end of input".to_string(),
+                Some('\n') | Some('\r') => "end of line".to_string(),
+                Some(c) => format!("`{}`", c),
+            }
+        };
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let error = &self.0;
+        let file = match &error.file {
+            Some(file) => file.display().to_string(),
+            None => "<input>".to_string(),
+        };
+        let gutter = " ".repeat(error.line.to_string().len());
+
+        writeln!(f, "expected {}, found {}", error.expected, error.found)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, file, error.line, error.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", error.line, error.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(error.column - 1),
+            "^".repeat(error.width)
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses every line of `input`, numbering errors by the line they occur on.
+pub fn parse_lines<T>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Parses `span`, a slice of `source`, as a number.
+pub fn parse_number<T: FromStr>(source: &str, span: &str) -> Result<T, ParseError> {
+    span.parse()
+        .map_err(|_| ParseError::at(source, span, "number"))
+}
+
+fn offset_of(source: &str, span: &str) -> usize {
+    let offset = (span.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+    assert!(
+        offset <= source.len() && offset + span.len() <= source.len(),
+        "span is not part of the source"
+    );
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_spans_by_line_and_character() {
+        let source = "first\nsé x yz\n";
+        let err = ParseError::at(source, &source[12..14], "a number");
+        assert_eq!((err.line(), err.column()), (2, 6));
+        assert_eq!(err.found(), "`yz`");
+        assert_eq!(err.expected(), "a number");
+    }
+
+    #[test]
+    fn within_moves_errors_out_of_their_part() {
+        let input = "a: 1\nb: 2 x\n";
+        let line = input.lines().nth(1).unwrap();
+        let value = &line[3..];
+        let err = ParseError::at(value, &value[2..], "end of line")
+            .within(line, value)
+            .within(input, line);
+        assert_eq!((err.line(), err.column()), (2, 6));
+        assert_eq!(err.found(), "`x`");
+
+        let err = err.after_lines(10).in_file("input.txt");
+        assert_eq!(err.line(), 12);
+        assert_eq!(err.file(), Some(Path::new("input.txt")));
+    }
+
+    #[test]
+    fn at_end_finds_the_end() {
+        let err = ParseError::at_end("12 ", "a colour");
+        assert_eq!((err.line(), err.column()), (1, 4));
+        assert_eq!(err.found(), "end of input");
+
+        let input = "12\n34";
+        let err = ParseError::at_end(&input[..2], "a colour").within(input, &input[..2]);
+        assert_eq!((err.line(), err.column()), (1, 3));
+        assert_eq!(err.found(), "end of line");
+    }
+
+    #[test]
+    fn display_underlines_the_span() {
+        let input = "x\n".repeat(9) + "Game 1: 3 rød";
+        let err = ParseError::at(&input, &input[28..], "a colour").in_file("day02/input.txt");
+        assert_eq!(
+            err.to_string(),
+            "\
+expected a colour, found `rød`
+  --> day02/input.txt:10:11
+   |
+10 | Game 1: 3 rød
+   |           ^^^"
+        );
+
+        let err = ParseError::at_end("Game 1", "`:`");
+        assert_eq!(
+            err.to_string(),
+            "\
+expected `:`, found end of input
+ --> <input>:1:7
+  |
+1 | Game 1
+  |       ^"
+        );
+    }
+
+    #[test]
+    fn parse_number_points_at_the_number() {
+        let line = "seeds: 79 x4";
+        let err = parse_number::<u64>(line, &line[10..]).unwrap_err();
+        assert_eq!(
+            (err.column(), err.expected(), err.found()),
+            (11, "number", "`x4`")
+        );
+        assert_eq!(parse_number::<u64>(line, &line[7..9]), Ok(79));
+    }
+}
@@ -0,0 +1,185 @@
+use common::{parse_number, ParseError};
+
+use crate::unicode::non_ascii_digits;
+
+/// Words and numerals the calibration decoder recognises, each standing for
+/// the digits of a number, so `twelve` contributes both 1 and 2.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dictionary {
+    entries: Vec<(String, Vec<u32>)>,
+    ignore_case: bool,
+}
+
+impl Dictionary {
+    pub fn new() -> Dictionary {
+        Dictionary::default()
+    }
+
+    /// The numerals `0` to `9`, as in part 1.
+    pub fn numerals() -> Dictionary {
+        (0..10).fold(Dictionary::new(), |dictionary, digit| {
+            dictionary.word(&digit.to_string(), digit)
+        })
+    }
+
+    /// The numerals and the English words `one` to `nine`, as in part 2.
+    pub fn english() -> Dictionary {
+        DIGIT_LETTERS
+            .iter()
+            .fold(Dictionary::numerals(), |dictionary, &(word, value)| {
+                dictionary.word(word, value)
+            })
+    }
+
+    /// Adds a word standing for the digits of `value`.
+    pub fn word(mut self, word: &str, value: u32) -> Dictionary {
+        assert!(!word.is_empty(), "dictionary words cannot be empty");
+        let digits = value
+            .to_string()
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .collect();
+        self.entries.push((word.to_string(), digits));
+        self
+    }
+
+    /// Adds every non-ASCII Unicode decimal digit, such as full-width `７`
+    /// or Arabic-Indic `٧`, as a numeral.
+    pub fn unicode_numerals(self) -> Dictionary {
+        non_ascii_digits().fold(self, |dictionary, (c, digit)| {
+            dictionary.word(c.encode_utf8(&mut [0; 4]), digit)
+        })
+    }
+
+    /// Adds all words of another dictionary.
+    pub fn extend(mut self, other: Dictionary) -> Dictionary {
+        self.entries.extend(other.entries);
+        self
+    }
+
+    /// Matches words regardless of case, lower-casing every character of
+    /// the words and of the lines as Unicode does.
+    pub fn ignore_case(mut self, ignore_case: bool) -> Dictionary {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        self.ignore_case
+    }
+
+    /// Words with the digits they stand for.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &[u32])> {
+        self.entries
+            .iter()
+            .map(|(word, digits)| (word.as_str(), digits.as_slice()))
+    }
+
+    /// Reads a dictionary with one `word number` pair per line. Blank lines
+    /// and lines starting with `#` are ignored.
+    pub fn parse(input: &str) -> Result<Dictionary, ParseError> {
+        let mut dictionary = Dictionary::new();
+        for line in input.lines() {
+            let entry = line.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+            let (word, value) =
+                parse_entry(entry).map_err(|e| e.within(line, entry).within(input, line))?;
+            dictionary = dictionary.word(word, value);
+        }
+        Ok(dictionary)
+    }
+}
+
+fn parse_entry(entry: &str) -> Result<(&str, u32), ParseError> {
+    let mut fields = entry.split_whitespace();
+    let word = fields.next().unwrap_or_default();
+    let value = fields
+        .next()
+        .ok_or_else(|| ParseError::at_end(entry, "a number after the word"))?;
+    if let Some(extra) = fields.next() {
+        return Err(ParseError::at(entry, extra, "end of line"));
+    }
+    Ok((word, parse_number(entry, value)?))
+}
+
+const DIGIT_LETTERS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Scanner;
+
+    fn words(dictionary: &Dictionary) -> Vec<(&str, Vec<u32>)> {
+        dictionary
+            .entries()
+            .map(|(word, digits)| (word, digits.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_skips_comments_and_blank_lines() {
+        let dictionary = Dictionary::parse("# French\n\n  un 1\ndeux\t2\n").unwrap();
+        assert_eq!(words(&dictionary), [("un", vec![1]), ("deux", vec![2])]);
+    }
+
+    #[test]
+    fn words_stand_for_every_digit_of_their_number() {
+        let dictionary = Dictionary::parse("twelve 12\nhundred 100").unwrap();
+        assert_eq!(
+            words(&dictionary),
+            [("twelve", vec![1, 2]), ("hundred", vec![1, 0, 0])]
+        );
+
+        let scanner = Scanner::new(&dictionary.extend(Dictionary::numerals()));
+        assert_eq!(scanner.first_and_last("twelve"), Some((1, 2)));
+        assert_eq!(scanner.first_and_last("3hundred"), Some((3, 0)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = Dictionary::parse("one 1\n  two\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 6));
+        assert_eq!(err.expected(), "a number after the word");
+
+        let err = Dictionary::parse("one 1 2").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 7));
+        assert_eq!(err.expected(), "end of line");
+
+        let err = Dictionary::parse("# numbers\none uno").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 5));
+    }
+
+    #[test]
+    fn ignore_case_folds_words_and_lines() {
+        let dictionary = Dictionary::parse("SIX 6\nzwölf 12\nΣΊΓΜΑ 5").unwrap();
+        let exact = Scanner::new(&dictionary);
+        assert_eq!(exact.first_and_last("sixZWÖLF"), None);
+
+        let folded = Scanner::new(&dictionary.ignore_case(true));
+        assert_eq!(folded.first_and_last("sixZWÖLF"), Some((6, 2)));
+        assert_eq!(folded.first_and_last("Σίγμα"), Some((5, 5)));
+    }
+
+    #[test]
+    fn ignore_case_reports_offsets_into_the_line() {
+        let dictionary = Dictionary::parse("ok 1").unwrap().ignore_case(true);
+        let scanner = Scanner::new(&dictionary);
+        // `İ` is two bytes long but lower-cases to three.
+        let line = "İİOKİ";
+        let found: Vec<_> = scanner.matches(line).map(|m| (m.start, m.end)).collect();
+        assert_eq!(found, [(4, 6)]);
+        assert_eq!(&line[4..6], "OK");
+    }
+}
@@ -0,0 +1,123 @@
+use common::{ParseError, Part, Solution};
+use std::str::FromStr;
+
+mod dictionary;
+mod scanner;
+mod stream;
+mod unicode;
+
+pub use dictionary::Dictionary;
+pub use scanner::{Match, Scanner};
+pub use stream::{calibrate_reader, StreamError};
+pub use unicode::decimal_digit;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = String;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<String, ParseError> {
+        Ok(input.to_string())
+    }
+
+    /// Part 1 only reads numerals and part 2 also spelled-out digits, so a
+    /// line can be fine for one part and have no digits for the other.
+    fn part1(input: &String) -> Result<u64, ParseError> {
+        sum_of_calibration_values(input, &scanner(Part::One))
+    }
+
+    fn part2(input: &String) -> Result<u64, ParseError> {
+        sum_of_calibration_values(input, &scanner(Part::Two))
+    }
+}
+
+fn scanner(part: Part) -> Scanner {
+    match part {
+        Part::One => Scanner::new(&Dictionary::numerals()),
+        Part::Two => Scanner::new(&Dictionary::english()),
+    }
+}
+
+/// What to do with a line that contains no digits at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NoDigits {
+    /// Stop with an error pointing at the line.
+    #[default]
+    Error,
+    /// Leave the line out of the sum.
+    Skip,
+    /// Count the line as a calibration value of 0.
+    Zero,
+}
+
+impl FromStr for NoDigits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<NoDigits, String> {
+        match s {
+            "error" => Ok(NoDigits::Error),
+            "skip" => Ok(NoDigits::Skip),
+            "zero" => Ok(NoDigits::Zero),
+            _ => Err(format!("expected error, skip or zero, found `{}`", s)),
+        }
+    }
+}
+
+/// Outcome of decoding a calibration document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub sum: u64,
+    /// Lines that had a calibration value.
+    pub decoded: usize,
+    /// Lines without digits that were skipped or counted as 0.
+    pub without_digits: usize,
+    /// 1-based number of the first line without digits.
+    pub first_without_digits: Option<usize>,
+}
+
+impl Summary {
+    /// Adds the summary of a part of the document that starts after
+    /// `lines_before` lines of the part summarised so far.
+    pub fn merge(&mut self, other: Summary, lines_before: usize) {
+        self.sum += other.sum;
+        self.decoded += other.decoded;
+        self.without_digits += other.without_digits;
+        if self.first_without_digits.is_none() {
+            self.first_without_digits = other.first_without_digits.map(|line| line + lines_before);
+        }
+    }
+}
+
+/// Sums the calibration values of all lines, decoded with `scanner`,
+/// handling lines without digits according to `no_digits`.
+pub fn calibrate(
+    input: &str,
+    scanner: &Scanner,
+    no_digits: NoDigits,
+) -> Result<Summary, ParseError> {
+    let mut summary = Summary::default();
+    for (i, line) in input.lines().enumerate() {
+        match scanner.first_and_last(line) {
+            Some((first, last)) => {
+                summary.sum += u64::from(first * 10 + last);
+                summary.decoded += 1;
+            }
+            None if no_digits == NoDigits::Error => {
+                return Err(ParseError::at(input, line, "a line with a digit"));
+            }
+            None => {
+                summary.without_digits += 1;
+                summary.first_without_digits.get_or_insert(i + 1);
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Sum of the calibration values of all lines, decoded with `scanner`.
+/// Fails on the first line without digits.
+pub fn sum_of_calibration_values(input: &str, scanner: &Scanner) -> Result<u64, ParseError> {
+    calibrate(input, scanner, NoDigits::Error).map(|summary| summary.sum)
+}
@@ -0,0 +1,239 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::{decimal_digit, Dictionary};
+
+/// A dictionary word found on a line: `line[start..end]` spells `digits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<'a> {
+    pub start: usize,
+    pub end: usize,
+    pub digits: &'a [u32],
+    /// Whether the match is written with digits rather than spelled out.
+    pub numeral: bool,
+}
+
+/// Aho-Corasick automaton that finds every occurrence of the words of a
+/// dictionary in one pass over a line, overlapping ones included, so
+/// `eightwo` yields both 8 and 2.
+pub struct Scanner {
+    states: Vec<State>,
+    words: Vec<Word>,
+    ignore_case: bool,
+}
+
+struct State {
+    next: [u32; 256],
+    /// Words ending in this state, as `(word length, index into words)`.
+    outputs: Vec<(usize, usize)>,
+}
+
+struct Word {
+    digits: Vec<u32>,
+    numeral: bool,
+}
+
+impl State {
+    fn new() -> State {
+        State {
+            next: [0; 256],
+            outputs: Vec::new(),
+        }
+    }
+}
+
+impl Scanner {
+    pub fn new(dictionary: &Dictionary) -> Scanner {
+        let mut states = vec![State::new()];
+        let mut words = Vec::new();
+
+        // Trie of all words; 0 doubles as "no edge yet" since the root is
+        // never the target of a forward edge.
+        for (word, word_digits) in dictionary.entries() {
+            let word = if dictionary.is_case_insensitive() {
+                fold(word)
+            } else {
+                word.to_string()
+            };
+            let mut state = 0;
+            for &byte in word.as_bytes() {
+                state = match states[state].next[byte as usize] {
+                    0 => {
+                        states.push(State::new());
+                        let new = states.len() - 1;
+                        states[state].next[byte as usize] = new as u32;
+                        new
+                    }
+                    next => next as usize,
+                };
+            }
+            states[state].outputs.push((word.len(), words.len()));
+            words.push(Word {
+                digits: word_digits.to_vec(),
+                numeral: word.chars().all(|c| decimal_digit(c).is_some()),
+            });
+        }
+
+        // Breadth-first, turn the trie into a complete transition table by
+        // following failure links, and inherit the outputs of the failure
+        // state so overlapping matches are reported.
+        let mut fail = vec![0; states.len()];
+        let mut queue: VecDeque<usize> = states[0]
+            .next
+            .iter()
+            .filter(|&&next| next != 0)
+            .map(|&next| next as usize)
+            .collect();
+        while let Some(state) = queue.pop_front() {
+            let inherited = states[fail[state]].outputs.clone();
+            states[state].outputs.extend(inherited);
+            for byte in 0..256 {
+                let next = states[state].next[byte] as usize;
+                let fallback = states[fail[state]].next[byte];
+                if next == 0 {
+                    states[state].next[byte] = fallback;
+                } else {
+                    fail[next] = fallback as usize;
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        Scanner {
+            states,
+            words,
+            ignore_case: dictionary.is_case_insensitive(),
+        }
+    }
+
+    /// Every match on the line, in the order in which they end.
+    pub fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match<'a>> + 'a {
+        // Without case, the automaton runs over the lower-cased line, and
+        // every byte of it remembers the character of `line` it came from,
+        // as lower-casing can change a character's length.
+        let (text, origins) = if self.ignore_case {
+            let mut folded = String::with_capacity(line.len());
+            let mut origins = Vec::with_capacity(line.len());
+            for (i, c) in line.char_indices() {
+                folded.extend(c.to_lowercase());
+                origins.resize(folded.len(), (i, i + c.len_utf8()));
+            }
+            (Cow::Owned(folded), Some(Rc::new(origins)))
+        } else {
+            (Cow::Borrowed(line), None)
+        };
+
+        let mut state = 0;
+        (0..text.len()).flat_map(move |i| {
+            state = self.states[state].next[text.as_bytes()[i] as usize] as usize;
+            let origins = origins.clone();
+            self.states[state].outputs.iter().map(move |&(len, word)| {
+                let (start, end) = match &origins {
+                    Some(origins) => (origins[i + 1 - len].0, origins[i].1),
+                    None => (i + 1 - len, i + 1),
+                };
+                Match {
+                    start,
+                    end,
+                    digits: &self.words[word].digits,
+                    numeral: self.words[word].numeral,
+                }
+            })
+        })
+    }
+
+    /// First digit of the leftmost match and last digit of the rightmost
+    /// match on the line.
+    pub fn first_and_last(&self, line: &str) -> Option<(u32, u32)> {
+        let (first, last) = self.first_and_last_matches(line)?;
+        Some((first.digits[0], *last.digits.last()?))
+    }
+
+    /// Leftmost and rightmost match on the line. When two matches start at
+    /// the same place the longer one wins.
+    pub fn first_and_last_matches<'a>(&'a self, line: &'a str) -> Option<(Match<'a>, Match<'a>)> {
+        let mut first: Option<Match> = None;
+        let mut last: Option<Match> = None;
+        for m in self.matches(line) {
+            if first.is_none_or(|f| m.start < f.start || (m.start == f.start && m.end > f.end)) {
+                first = Some(m);
+            }
+            if last.is_none_or(|l| (m.start, m.end) > (l.start, l.end)) {
+                last = Some(m);
+            }
+        }
+        Some((first?, last?))
+    }
+}
+
+/// Lower-cases `text` one character at a time, the same way
+/// [`Scanner::matches`] lower-cases the lines.
+fn fold(text: &str) -> String {
+    text.chars().flat_map(char::to_lowercase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(scanner: &Scanner, line: &str) -> Vec<(usize, usize, Vec<u32>)> {
+        scanner
+            .matches(line)
+            .map(|m| (m.start, m.end, m.digits.to_vec()))
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let scanner = Scanner::new(&Dictionary::english());
+        assert_eq!(
+            matches(&scanner, "eightwo"),
+            [(0, 5, vec![8]), (4, 7, vec![2])]
+        );
+        assert_eq!(
+            matches(&scanner, "oneight"),
+            [(0, 3, vec![1]), (2, 7, vec![8])]
+        );
+        assert_eq!(
+            matches(&scanner, "sevenine"),
+            [(0, 5, vec![7]), (4, 8, vec![9])]
+        );
+        assert_eq!(scanner.first_and_last("oneight"), Some((1, 8)));
+        assert_eq!(scanner.first_and_last("xsevenine4"), Some((7, 4)));
+    }
+
+    #[test]
+    fn numerals_and_words() {
+        let scanner = Scanner::new(&Dictionary::english());
+        let found: Vec<_> = scanner.matches("a1two").map(|m| m.numeral).collect();
+        assert_eq!(found, [true, false]);
+        assert_eq!(scanner.first_and_last("a1two"), Some((1, 2)));
+        assert_eq!(scanner.first_and_last("5"), Some((5, 5)));
+    }
+
+    #[test]
+    fn longer_match_wins_at_the_same_start() {
+        let dictionary = Dictionary::parse("seven 7\nseventeen 17\nteen 10").unwrap();
+        let scanner = Scanner::new(&dictionary);
+        let (first, last) = scanner.first_and_last_matches("seventeen").unwrap();
+        assert_eq!((first.start, first.end), (0, 9));
+        assert_eq!((last.start, last.end), (5, 9));
+        assert_eq!(scanner.first_and_last("seventeen"), Some((1, 0)));
+        assert_eq!(scanner.first_and_last("xseventeenx"), Some((1, 0)));
+        assert_eq!(scanner.first_and_last("seventee"), Some((7, 7)));
+    }
+
+    #[test]
+    fn lines_without_matches() {
+        let scanner = Scanner::new(&Dictionary::english());
+        assert_eq!(scanner.first_and_last(""), None);
+        assert_eq!(scanner.first_and_last("abcdefg"), None);
+        assert_eq!(scanner.first_and_last("on tw thre"), None);
+        assert_eq!(scanner.matches("nin").count(), 0);
+        assert_eq!(
+            Scanner::new(&Dictionary::new()).first_and_last("1two"),
+            None
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use common::ParseError;
+use rayon::prelude::*;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::{calibrate, NoDigits, Scanner, Summary};
+
+/// Lines held in memory per chunk.
+const CHUNK_LINES: usize = 1 << 16;
+
+/// Failure to calibrate a document read from a stream.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "{}", err),
+            StreamError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+/// Like [`calibrate`], but reads the document from `reader` a chunk of lines
+/// at a time, so memory use does not grow with its size. With `parallel`,
+/// one chunk per thread is decoded at once.
+pub fn calibrate_reader(
+    mut reader: impl BufRead,
+    scanner: &Scanner,
+    no_digits: NoDigits,
+    parallel: bool,
+) -> Result<Summary, StreamError> {
+    let batch = if parallel {
+        rayon::current_num_threads()
+    } else {
+        1
+    };
+    let mut summary = Summary::default();
+    let mut lines_before = 0;
+    loop {
+        let mut chunks = Vec::new();
+        while chunks.len() < batch {
+            match read_chunk(&mut reader).map_err(StreamError::Io)? {
+                (_, 0) => break,
+                chunk => chunks.push(chunk),
+            }
+        }
+        if chunks.is_empty() {
+            return Ok(summary);
+        }
+
+        let results: Vec<_> = chunks
+            .par_iter()
+            .map(|(text, _)| calibrate(text, scanner, no_digits))
+            .collect();
+        for ((_, lines), result) in chunks.iter().zip(results) {
+            let chunk = result.map_err(|err| StreamError::Parse(err.after_lines(lines_before)))?;
+            summary.merge(chunk, lines_before);
+            lines_before += lines;
+        }
+    }
+}
+
+/// Reads up to `CHUNK_LINES` lines, returning them with their count.
+fn read_chunk(reader: &mut impl BufRead) -> io::Result<(String, usize)> {
+    let mut text = String::new();
+    let mut lines = 0;
+    while lines < CHUNK_LINES && reader.read_line(&mut text)? > 0 {
+        lines += 1;
+    }
+    Ok((text, lines))
+}
@@ -0,0 +1,113 @@
+/// Code points of the zeros of all Unicode decimal digit (`Nd`) sets, in
+/// ascending order. Every set is ten consecutive code points from 0 to 9.
+/// Generated from the Unicode 17.0.0 character database.
+const DIGIT_ZEROS: [u32; 77] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950,
+    0x1FBF0,
+];
+
+/// Value of any Unicode decimal digit, such as `7`, full-width `７`,
+/// Arabic-Indic `٧` or Devanagari `७`.
+pub fn decimal_digit(c: char) -> Option<u32> {
+    let c = c as u32;
+    let zero = match DIGIT_ZEROS.binary_search(&c) {
+        Ok(i) => DIGIT_ZEROS[i],
+        Err(0) => return None,
+        Err(i) => DIGIT_ZEROS[i - 1],
+    };
+    (c - zero < 10).then_some(c - zero)
+}
+
+/// Every non-ASCII decimal digit with its value.
+pub(crate) fn non_ascii_digits() -> impl Iterator<Item = (char, u32)> {
+    DIGIT_ZEROS[1..].iter().flat_map(|&zero| {
+        (0..10).filter_map(move |digit| Some((char::from_u32(zero + digit)?, digit)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calibrate, Dictionary, NoDigits, Scanner};
+
+    fn sum(dictionary: Dictionary, input: &str) -> u64 {
+        calibrate(input, &Scanner::new(&dictionary), NoDigits::Error)
+            .unwrap()
+            .sum
+    }
+
+    /// Starts of the runs of ten or more numeric characters that are not
+    /// decimal digits, such as subscripts, circled numbers or numerals
+    /// without a zero. A set of decimal digits missing from the table shows
+    /// up as a new run.
+    const OTHER_NUMBERS: [u32; 33] = [
+        0xF2A, 0x1369, 0x17F0, 0x2080, 0x2150, 0x2460, 0x24EA, 0x2776, 0x3220, 0x3251, 0x3280,
+        0x32B1, 0xA6E6, 0x10107, 0x10140, 0x102E1, 0x109C0, 0x109D2, 0x10E60, 0x10F1D, 0x11052,
+        0x111E1, 0x11C5A, 0x11FC0, 0x12400, 0x16E80, 0x1D2C0, 0x1D2E0, 0x1D360, 0x1EC71, 0x1ED01,
+        0x1ED2F, 0x1F100,
+    ];
+
+    #[test]
+    fn every_decimal_digit_is_known() {
+        for zero in DIGIT_ZEROS {
+            for digit in 0..10 {
+                let c = char::from_u32(zero + digit).unwrap();
+                assert!(c.is_numeric(), "{:?}", c);
+                assert_eq!(decimal_digit(c), Some(digit), "{:?}", c);
+            }
+        }
+
+        let mut runs = Vec::new();
+        let mut run: Option<(u32, u32)> = None;
+        for code in 0..=char::MAX as u32 + 1 {
+            let other =
+                char::from_u32(code).is_some_and(|c| c.is_numeric() && decimal_digit(c).is_none());
+            run = match (run, other) {
+                (None, true) => Some((code, 1)),
+                (Some((start, len)), true) => Some((start, len + 1)),
+                (Some((start, len)), false) => {
+                    if len >= 10 {
+                        runs.push(start);
+                    }
+                    None
+                }
+                (None, false) => None,
+            };
+        }
+        let runs: Vec<String> = runs.iter().map(|start| format!("{:#X}", start)).collect();
+        let expected: Vec<String> = OTHER_NUMBERS
+            .iter()
+            .map(|start| format!("{:#X}", start))
+            .collect();
+        assert_eq!(runs, expected);
+
+        assert_eq!(decimal_digit('a'), None);
+        assert_eq!(decimal_digit('٫'), None);
+        assert_eq!(decimal_digit('/'), None);
+        assert_eq!(decimal_digit('½'), None);
+    }
+
+    #[test]
+    fn mixed_script_lines() {
+        let dictionary = Dictionary::english().unicode_numerals();
+        assert_eq!(sum(dictionary.clone(), "a١b٢c"), 12);
+        assert_eq!(sum(dictionary.clone(), "５ｘ７"), 57);
+        assert_eq!(sum(dictionary.clone(), "३abc"), 33);
+        assert_eq!(sum(dictionary.clone(), "x٤seven９"), 49);
+        assert_eq!(sum(dictionary.clone(), "eight१two"), 82);
+        assert_eq!(sum(dictionary, "a١b٢c\n５ｘ７\n3"), 12 + 57 + 33);
+    }
+
+    #[test]
+    fn ascii_mode_ignores_other_scripts() {
+        assert_eq!(sum(Dictionary::numerals(), "٤1٥"), 11);
+        let scanner = Scanner::new(&Dictionary::numerals());
+        assert!(calibrate("٤٥", &scanner, NoDigits::Error).is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+
+use crate::{fewest_balls, Bag, Game};
+
+/// How many cubes of one colour the sets showed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    pub colour: String,
+    /// Number of sets by cube count. Sets without the colour count as 0.
+    pub histogram: BTreeMap<u32, usize>,
+}
+
+impl Distribution {
+    pub fn sets(&self) -> usize {
+        self.histogram.values().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        let total: u64 = self
+            .histogram
+            .iter()
+            .map(|(&count, &sets)| u64::from(count) * sets as u64)
+            .sum();
+        total as f64 / self.sets().max(1) as f64
+    }
+
+    pub fn max(&self) -> u32 {
+        self.histogram.keys().next_back().copied().unwrap_or(0)
+    }
+}
+
+/// Maximum-likelihood bag found by [`estimate_bag`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub bag: Bag,
+    pub log_likelihood: f64,
+    /// Whether some colour stopped at the cap while the likelihood was still
+    /// rising, meaning the estimate is a lower bound rather than a maximum.
+    pub capped: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub games: usize,
+    pub sets: usize,
+    pub distributions: Vec<Distribution>,
+    /// Fewest cubes of every colour that make all games possible.
+    pub minimal_bag: Bag,
+    /// Ids of the games that show the minimal bag's count of each colour,
+    /// the games that would become impossible with one cube fewer.
+    pub binding: BTreeMap<String, Vec<u32>>,
+    pub estimate: Estimate,
+}
+
+/// Statistics of a game log and what they say about the bag. No colour is
+/// estimated to have more than `cap` cubes.
+pub fn analyse(games: &[Game], cap: u32) -> Report {
+    let minimal_bag = minimal_bag(games);
+    let sets = games.iter().map(|game| game.sets.len()).sum();
+
+    let distributions = minimal_bag
+        .cubes()
+        .map(|(colour, _)| {
+            let mut histogram = BTreeMap::new();
+            for set in games.iter().flat_map(|game| &game.sets) {
+                *histogram.entry(set.count(colour)).or_insert(0) += 1;
+            }
+            Distribution {
+                colour: colour.to_string(),
+                histogram,
+            }
+        })
+        .collect();
+
+    let binding = minimal_bag
+        .cubes()
+        .map(|(colour, needed)| {
+            let games = games
+                .iter()
+                .filter(|game| game.sets.iter().any(|set| set.count(colour) == needed))
+                .map(|game| game.id)
+                .collect();
+            (colour.to_string(), games)
+        })
+        .collect();
+
+    Report {
+        games: games.len(),
+        sets,
+        distributions,
+        estimate: estimate_bag(games, cap),
+        minimal_bag,
+        binding,
+    }
+}
+
+/// Fewest cubes of every colour used in any game that make all of them
+/// possible.
+pub fn minimal_bag(games: &[Game]) -> Bag {
+    let mut fewest = BTreeMap::new();
+    for (colour, count) in games.iter().flat_map(fewest_balls) {
+        let max = fewest.entry(colour).or_insert(0);
+        *max = count.max(*max);
+    }
+    fewest.into_iter().collect()
+}
+
+/// Estimates the bag by maximum likelihood, taking every set as cubes drawn
+/// without replacement from the full bag, so a set's likelihood is the
+/// multivariate hypergeometric probability of its counts.
+///
+/// Searches the integer counts by coordinate ascent, starting from the
+/// minimal bag with large steps that are halved whenever no single colour can
+/// be improved. Counts stay between the minimal bag and `cap`, as the
+/// likelihood can keep growing with the bag's size.
+///
+/// Unless the sets show the bag running out of some colour, a bigger bag
+/// explains them at least as well, so for most logs the estimate ends up at
+/// the cap with [`Estimate::capped`] set. Its counts are then only good for
+/// the ratios between the colours, not for the size of the bag.
+pub fn estimate_bag(games: &[Game], cap: u32) -> Estimate {
+    let minimal = minimal_bag(games);
+    let colours: Vec<&str> = minimal.cubes().map(|(colour, _)| colour).collect();
+    let lower: Vec<u32> = minimal.cubes().map(|(_, count)| count).collect();
+    let cap = cap.max(lower.iter().copied().max().unwrap_or(0));
+    let draws: Vec<Vec<u32>> = games
+        .iter()
+        .flat_map(|game| &game.sets)
+        .map(|set| colours.iter().map(|colour| set.count(colour)).collect())
+        .collect();
+
+    let mut counts = lower.clone();
+    let mut best = log_likelihood(&draws, &counts);
+    let mut step = (cap / 4).max(1);
+    loop {
+        let mut improved = false;
+        for c in 0..counts.len() {
+            for candidate in [
+                counts[c].saturating_add(step),
+                counts[c].saturating_sub(step),
+            ] {
+                let candidate = candidate.clamp(lower[c], cap);
+                if candidate == counts[c] {
+                    continue;
+                }
+                let previous = std::mem::replace(&mut counts[c], candidate);
+                let likelihood = log_likelihood(&draws, &counts);
+                if likelihood > best {
+                    best = likelihood;
+                    improved = true;
+                } else {
+                    counts[c] = previous;
+                }
+            }
+        }
+        if !improved {
+            if step == 1 {
+                break;
+            }
+            step /= 2;
+        }
+    }
+
+    // At the cap, check whether one more cube would still have helped.
+    let capped = (0..counts.len()).any(|c| {
+        counts[c] == cap
+            && counts[c].checked_add(1).is_some_and(|one_more| {
+                let mut more = counts.clone();
+                more[c] = one_more;
+                log_likelihood(&draws, &more) > best
+            })
+    });
+
+    Estimate {
+        bag: colours.into_iter().zip(counts).collect(),
+        log_likelihood: best,
+        capped,
+    }
+}
+
+/// Log-likelihood of drawing every set of `draws` from a bag with `counts`
+/// cubes of each colour.
+fn log_likelihood(draws: &[Vec<u32>], counts: &[u32]) -> f64 {
+    let total: u64 = counts.iter().map(|&count| u64::from(count)).sum();
+    draws
+        .iter()
+        .map(|draw| {
+            let drawn: u64 = draw.iter().map(|&count| u64::from(count)).sum();
+            let ways: f64 = draw
+                .iter()
+                .zip(counts)
+                .map(|(&k, &n)| ln_choose(n.into(), k.into()))
+                .sum();
+            ways - ln_choose(total, drawn)
+        })
+        .sum()
+}
+
+fn ln_choose(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    (0..k)
+        .map(|i| ((n - i) as f64).ln() - ((i + 1) as f64).ln())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_games;
+
+    fn example() -> Vec<Game> {
+        parse_games(include_str!("example.txt")).unwrap()
+    }
+
+    #[test]
+    fn minimal_bag_and_binding_games() {
+        let report = analyse(&example(), 100);
+        assert_eq!(report.minimal_bag.to_string(), "15 blue, 13 green, 20 red");
+        assert_eq!(minimal_bag(&example()), report.minimal_bag);
+        assert_eq!(report.binding["blue"], [4]);
+        assert_eq!(report.binding["green"], [3]);
+        assert_eq!(report.binding["red"], [3]);
+        assert_eq!((report.games, report.sets), (5, 14));
+    }
+
+    #[test]
+    fn distributions_count_missing_colours_as_zero() {
+        let report = analyse(&example(), 100);
+        let red = &report.distributions[2];
+        assert_eq!(red.colour, "red");
+        assert_eq!(red.sets(), 14);
+        assert_eq!(red.histogram[&0], 3);
+        assert_eq!(red.max(), 20);
+        assert!((red.mean() - 61.0 / 14.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mean_of_an_empty_distribution() {
+        let empty = Distribution {
+            colour: "red".to_string(),
+            histogram: BTreeMap::new(),
+        };
+        assert_eq!(empty.mean(), 0.0);
+        assert_eq!(empty.max(), 0);
+    }
+
+    #[test]
+    fn estimate_stays_at_a_bag_that_was_emptied() {
+        let games = parse_games("Game 1: 3 red, 3 blue; 3 red, 3 blue").unwrap();
+        let estimate = estimate_bag(&games, 1000);
+        assert_eq!(estimate.bag.to_string(), "3 blue, 3 red");
+        assert_eq!(estimate.log_likelihood, 0.0);
+        assert!(!estimate.capped);
+    }
+
+    #[test]
+    fn estimate_reports_the_cap() {
+        let estimate = estimate_bag(&example(), 30);
+        assert!(estimate.capped);
+        assert!(estimate.bag.cubes().any(|(_, count)| count == 30));
+        assert!(estimate.bag.cubes().all(|(_, count)| count <= 30));
+    }
+
+    #[test]
+    fn estimate_never_goes_below_the_minimal_bag() {
+        let minimal = minimal_bag(&example());
+        let estimate = estimate_bag(&example(), 5);
+        for (colour, count) in estimate.bag.cubes() {
+            assert!((minimal.count(colour)..=20).contains(&count), "{}", colour);
+        }
+        assert_eq!(estimate.bag.count("red"), 20);
+    }
+
+    #[test]
+    fn estimate_of_huge_counts() {
+        let games = parse_games("Game 1: 4294967295 red, 4294967295 blue").unwrap();
+        let estimate = estimate_bag(&games, 10);
+        assert_eq!(estimate.bag, minimal_bag(&games));
+        assert_eq!(estimate.log_likelihood, 0.0);
+        assert!(!estimate.capped);
+    }
+}
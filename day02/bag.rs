@@ -0,0 +1,140 @@
+use common::ParseError;
+use std::fmt;
+
+use crate::{count, insert, parse_set, Game};
+
+/// Cubes in the bag, by colour, in the order they were given. Colours not in
+/// the bag have 0 cubes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bag {
+    cubes: Vec<(String, u32)>,
+}
+
+/// First cube count of a game that the bag cannot supply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 0-based index of the set within the game.
+    pub set: usize,
+    pub colour: String,
+    pub shown: u32,
+    pub in_bag: u32,
+}
+
+impl Bag {
+    /// The bag of part 1: 12 red, 13 green and 14 blue cubes.
+    pub fn part1() -> Bag {
+        Bag::parse("12 red, 13 green, 14 blue").unwrap()
+    }
+
+    /// Parses cube counts written like a set, e.g. `12 red, 13 green`.
+    pub fn parse(cubes: &str) -> Result<Bag, ParseError> {
+        let set = parse_set(cubes, cubes)?;
+        Ok(Bag { cubes: set.cubes })
+    }
+
+    pub fn count(&self, colour: &str) -> u32 {
+        count(&self.cubes, colour)
+    }
+
+    pub fn cubes(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(colour, count)| (colour.as_str(), *count))
+    }
+
+    /// The first set and colour, in the order they are written, that shows
+    /// more cubes than the bag holds.
+    pub fn check(&self, game: &Game) -> Result<(), Violation> {
+        for (i, set) in game.sets.iter().enumerate() {
+            for (colour, shown) in set.cubes() {
+                let in_bag = self.count(colour);
+                if shown > in_bag {
+                    return Err(Violation {
+                        set: i,
+                        colour: colour.to_string(),
+                        shown,
+                        in_bag,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn allows(&self, game: &Game) -> bool {
+        self.check(game).is_ok()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, u32)> for Bag {
+    fn from_iter<I: IntoIterator<Item = (S, u32)>>(cubes: I) -> Bag {
+        let mut bag = Bag { cubes: Vec::new() };
+        for (colour, count) in cubes {
+            insert(&mut bag.cubes, colour.into(), count);
+        }
+        bag
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cubes: Vec<_> = self
+            .cubes
+            .iter()
+            .map(|(colour, count)| format!("{} {}", count, colour))
+            .collect();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "set {} shows {} {}, the bag holds {}",
+            self.set + 1,
+            self.shown,
+            self.colour,
+            self.in_bag
+        )
+    }
+}
+
+/// Parses named bags, one `name: cubes` pair per line. Blank lines and lines
+/// starting with `#` are ignored.
+pub fn parse_bags(input: &str) -> Result<Vec<(String, Bag)>, ParseError> {
+    let mut bags = Vec::new();
+    for line in input.lines() {
+        let entry = line.trim();
+        if entry.is_empty() || entry.starts_with('#') {
+            continue;
+        }
+        let (name, cubes) = entry
+            .split_once(':')
+            .ok_or_else(|| ParseError::at_end(line, "`:`").within(input, line))?;
+        let bag = Bag::parse(cubes).map_err(|e| e.within(input, cubes))?;
+        bags.push((name.trim().to_string(), bag));
+    }
+    Ok(bags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_game;
+
+    #[test]
+    fn check_reports_the_first_colour_written() {
+        let game = parse_game("Game 1: 3 green; 20 red, 20 blue").unwrap();
+        let violation = Bag::part1().check(&game).unwrap_err();
+        assert_eq!(violation.set, 1);
+        assert_eq!(violation.colour, "red");
+        assert_eq!(violation.shown, 20);
+        assert_eq!(violation.in_bag, 12);
+    }
+
+    #[test]
+    fn display_keeps_the_written_order() {
+        assert_eq!(Bag::part1().to_string(), "12 red, 13 green, 14 blue");
+    }
+}
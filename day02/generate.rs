@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+
+use crate::Bag;
+
+/// SplitMix64, a small seeded generator so logs can be reproduced exactly.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+}
+
+/// Settings for a synthetic game log. The colours of the bag are the
+/// palette the games use.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    pub games: usize,
+    /// Every game has between 1 and this many sets.
+    pub max_sets: usize,
+    pub bag: Bag,
+    /// Probability that a game shows more cubes of a colour than the bag
+    /// holds in one of its sets.
+    pub impossible: f64,
+    pub seed: u64,
+}
+
+/// What the generator knows about a game it wrote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Truth {
+    pub id: u32,
+    pub possible: bool,
+    /// Most cubes of each colour the game showed at once.
+    pub fewest: BTreeMap<String, u32>,
+}
+
+impl Default for Generator {
+    fn default() -> Generator {
+        Generator {
+            games: 100,
+            max_sets: 6,
+            bag: Bag::part1(),
+            impossible: 0.3,
+            seed: 0,
+        }
+    }
+}
+
+impl Generator {
+    /// Writes a game log in the puzzle's format along with the truth about
+    /// every game. Sets are drawn without replacement from the bag, except
+    /// for the one set an impossible game overdraws. Fails if the bag holds
+    /// no cubes, as every set must show at least one.
+    pub fn generate(&self) -> Result<(String, Vec<Truth>), String> {
+        if self.bag.cubes().all(|(_, count)| count == 0) {
+            return Err(format!("the bag `{}` holds no cubes to draw", self.bag));
+        }
+        let mut rng = Rng::new(self.seed);
+        let cubes: Vec<&str> = self
+            .bag
+            .cubes()
+            .flat_map(|(colour, count)| std::iter::repeat_n(colour, count as usize))
+            .collect();
+        let colours: Vec<&str> = self.bag.cubes().map(|(colour, _)| colour).collect();
+
+        let mut log = String::new();
+        let mut truths = Vec::new();
+        for id in 1..=self.games as u32 {
+            let sets = 1 + rng.below(self.max_sets.max(1) as u64) as usize;
+            let mut drawn: Vec<Vec<(&str, u32)>> = (0..sets)
+                .map(|_| draw(&mut rng, &cubes, &colours))
+                .collect();
+
+            let possible = !rng.chance(self.impossible);
+            if !possible {
+                let set = &mut drawn[rng.below(sets as u64) as usize];
+                let colour = colours[rng.below(colours.len() as u64) as usize];
+                let too_many = self.bag.count(colour) + 1 + rng.below(3) as u32;
+                match set.iter_mut().find(|(c, _)| *c == colour) {
+                    Some(entry) => entry.1 = too_many,
+                    None => set.push((colour, too_many)),
+                }
+            }
+
+            let mut fewest = BTreeMap::new();
+            for &(colour, count) in drawn.iter().flatten() {
+                let max = fewest.entry(colour.to_string()).or_insert(0);
+                *max = count.max(*max);
+            }
+            let sets: Vec<String> = drawn
+                .iter()
+                .map(|set| {
+                    let cubes: Vec<_> = set.iter().map(|(c, n)| format!("{} {}", n, c)).collect();
+                    cubes.join(", ")
+                })
+                .collect();
+            log.push_str(&format!("Game {}: {}\n", id, sets.join("; ")));
+            truths.push(Truth {
+                id,
+                possible,
+                fewest,
+            });
+        }
+        Ok((log, truths))
+    }
+}
+
+/// Draws between one and all cubes from the bag without replacement,
+/// listing the colours drawn in random order.
+fn draw<'a>(rng: &mut Rng, cubes: &[&'a str], colours: &[&'a str]) -> Vec<(&'a str, u32)> {
+    let mut cubes = cubes.to_vec();
+    let n = 1 + rng.below(cubes.len() as u64) as usize;
+    // Partial Fisher-Yates shuffle: the first n cubes are the draw.
+    for i in 0..n {
+        let j = i + rng.below((cubes.len() - i) as u64) as usize;
+        cubes.swap(i, j);
+    }
+    let mut set: Vec<(&str, u32)> = colours
+        .iter()
+        .map(|&colour| {
+            let count = cubes.iter().take(n).filter(|&&c| c == colour).count();
+            (colour, count as u32)
+        })
+        .filter(|&(_, count)| count > 0)
+        .collect();
+    for i in (1..set.len()).rev() {
+        set.swap(i, rng.below(i as u64 + 1) as usize);
+    }
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_games, power};
+
+    fn generators() -> impl Iterator<Item = Generator> {
+        let bags = [
+            Bag::part1(),
+            Bag::parse("3 yellow, 1 purple").unwrap(),
+            Bag::parse("20 red, 2 green, 7 blue, 5 teal, 1 orange").unwrap(),
+        ];
+        (0..20).flat_map(move |seed| {
+            bags.clone().into_iter().map(move |bag| Generator {
+                games: 50,
+                max_sets: 8,
+                bag,
+                impossible: 0.4,
+                seed,
+            })
+        })
+    }
+
+    #[test]
+    fn same_seed_same_log() {
+        let generator = Generator::default();
+        assert_eq!(generator.generate(), generator.generate());
+    }
+
+    #[test]
+    fn refuses_a_bag_without_cubes() {
+        let generator = Generator {
+            bag: Bag::parse("0 red, 0 blue").unwrap(),
+            ..Generator::default()
+        };
+        assert!(generator.generate().is_err());
+    }
+
+    #[test]
+    fn allowed_games_match_the_ground_truth() {
+        for generator in generators() {
+            let (log, truths) = generator.generate().unwrap();
+            let games = parse_games(&log).unwrap();
+            assert_eq!(games.len(), truths.len());
+            for (game, truth) in games.iter().zip(&truths) {
+                assert_eq!(game.id, truth.id);
+                assert_eq!(generator.bag.allows(game), truth.possible, "{}", log);
+            }
+        }
+    }
+
+    #[test]
+    fn power_is_the_true_minimal_bag() {
+        for generator in generators() {
+            let (log, truths) = generator.generate().unwrap();
+            let games = parse_games(&log).unwrap();
+            for (game, truth) in games.iter().zip(&truths) {
+                let minimal: u32 = truth.fewest.values().product();
+                assert_eq!(power(game), minimal, "game {} of {}", game.id, log);
+                // Every colour of a set is needed at least that often, and
+                // every colour of the game at least once.
+                for set in game.sets() {
+                    let shown: u32 = set.cubes().map(|(_, count)| count).product();
+                    assert!(power(game) >= shown, "game {} of {}", game.id, log);
+                }
+                if truth.possible {
+                    let bag: u32 = truth
+                        .fewest
+                        .keys()
+                        .map(|c| generator.bag.count(c))
+                        .product();
+                    assert!(power(game) <= bag, "game {} of {}", game.id, log);
+                }
+            }
+        }
+    }
+}
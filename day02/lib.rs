@@ -0,0 +1,133 @@
+use common::{parse_lines, parse_number, ParseError, Solution};
+use std::collections::BTreeMap;
+
+mod analysis;
+mod bag;
+mod generate;
+mod query;
+mod strict;
+
+pub use analysis::{analyse, estimate_bag, minimal_bag, Distribution, Estimate, Report};
+pub use bag::{parse_bags, Bag, Violation};
+pub use generate::{Generator, Rng, Truth};
+pub use query::{Measure, Query};
+pub use strict::{parse_games_strict, COLOURS};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+        parse_games(input)
+    }
+
+    fn part1(games: &Vec<Game>) -> Result<u32, ParseError> {
+        let bag = Bag::part1();
+        Ok(games
+            .iter()
+            .filter(|game| bag.allows(game))
+            .map(|game| game.id)
+            .sum())
+    }
+
+    fn part2(games: &Vec<Game>) -> Result<u32, ParseError> {
+        Ok(games.iter().map(power).sum())
+    }
+}
+
+pub struct Game {
+    id: u32,
+    sets: Vec<Set>,
+}
+
+impl Game {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn sets(&self) -> &[Set] {
+        &self.sets
+    }
+}
+
+/// Cubes shown at once, by colour, in the order they are written.
+pub struct Set {
+    cubes: Vec<(String, u32)>,
+}
+
+impl Set {
+    /// Number of cubes of `colour`, 0 if none were shown.
+    pub fn count(&self, colour: &str) -> u32 {
+        count(&self.cubes, colour)
+    }
+
+    pub fn cubes(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(colour, count)| (colour.as_str(), *count))
+    }
+}
+
+fn count(cubes: &[(String, u32)], colour: &str) -> u32 {
+    cubes
+        .iter()
+        .find(|(c, _)| c == colour)
+        .map_or(0, |&(_, count)| count)
+}
+
+/// Sets the count of `colour`, keeping its place if it is already listed.
+fn insert(cubes: &mut Vec<(String, u32)>, colour: String, count: u32) {
+    match cubes.iter_mut().find(|(c, _)| *c == colour) {
+        Some(entry) => entry.1 = count,
+        None => cubes.push((colour, count)),
+    }
+}
+
+fn power(game: &Game) -> u32 {
+    fewest_balls(game).values().product()
+}
+
+/// Fewest cubes of every colour used in the game that make it possible.
+fn fewest_balls(game: &Game) -> BTreeMap<&str, u32> {
+    let mut fewest = BTreeMap::new();
+    for (colour, count) in game.sets.iter().flat_map(Set::cubes) {
+        let max = fewest.entry(colour).or_insert(0);
+        *max = count.max(*max);
+    }
+    fewest
+}
+
+fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse_lines(input, parse_game)
+}
+
+fn parse_game(line: &str) -> Result<Game, ParseError> {
+    let (header, sets) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at_end(line, "`:`"))?;
+    let id = header.split(' ').next_back().unwrap_or(header);
+    let id: u32 = parse_number(line, id)?;
+    let sets = parse_sets(line, sets)?;
+
+    Ok(Game { id, sets })
+}
+
+fn parse_sets(line: &str, sets: &str) -> Result<Vec<Set>, ParseError> {
+    sets.split(';').map(|set| parse_set(line, set)).collect()
+}
+
+fn parse_set(line: &str, set: &str) -> Result<Set, ParseError> {
+    let mut cubes = Vec::new();
+
+    for s in set.split(',') {
+        let s = s.trim();
+        let parts: Vec<_> = s.split(' ').collect();
+        let count: u32 = parse_number(line, parts[0])?;
+        let colour = parts[parts.len() - 1].trim();
+        insert(&mut cubes, colour.to_string(), count);
+    }
+    Ok(Set { cubes })
+}
@@ -0,0 +1,386 @@
+//! Filter expressions over games, e.g.
+//! `any(blue > 10) and all(red <= 3)` or `max(green) >= 5 and not id == 3`.
+//!
+//! ```text
+//! query      = and ("or" and)*
+//! and        = unary ("and" unary)*
+//! unary      = "not" unary | "(" query ")" | "true"
+//!            | ("any" | "all") "(" query ")" | value op value
+//! value      = number | "id" | "sets" | "power"
+//!            | ("max" | "min" | "sum") "(" value ")"
+//!            | colour | "total"
+//! op         = "<" | "<=" | ">" | ">=" | "==" | "!="
+//! ```
+//!
+//! `any(..)` and `all(..)` test every set of a game, and `max(..)`, `min(..)`
+//! and `sum(..)` aggregate a value over every set. Inside them a colour is
+//! the number of cubes of that colour in the set and `total` all cubes in
+//! the set; outside them only game values are allowed.
+
+use common::{parse_number, ParseError};
+
+use crate::{power, Game, Set};
+
+/// A condition games can be filtered by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query(Condition);
+
+/// A number computed for every game, such as `id` or `max(red)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Measure(Value);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Condition {
+    True,
+    Not(Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Any(Box<Condition>),
+    All(Box<Condition>),
+    Compare(Value, Op, Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Number(u64),
+    Id,
+    Sets,
+    Power,
+    Colour(String),
+    Total,
+    Max(Box<Value>),
+    Min(Box<Value>),
+    Sum(Box<Value>),
+}
+
+impl Query {
+    pub fn parse(source: &str) -> Result<Query, ParseError> {
+        let mut parser = Parser::new(source);
+        let condition = parser.query(false)?;
+        parser.end()?;
+        Ok(Query(condition))
+    }
+
+    pub fn matches(&self, game: &Game) -> bool {
+        self.0.holds(game, None)
+    }
+}
+
+impl Measure {
+    pub fn parse(source: &str) -> Result<Measure, ParseError> {
+        let mut parser = Parser::new(source);
+        let value = parser.value(false)?;
+        parser.end()?;
+        Ok(Measure(value))
+    }
+
+    pub fn of(&self, game: &Game) -> u64 {
+        self.0.of(game, None)
+    }
+}
+
+impl Condition {
+    fn holds(&self, game: &Game, set: Option<&Set>) -> bool {
+        match self {
+            Condition::True => true,
+            Condition::Not(condition) => !condition.holds(game, set),
+            Condition::And(a, b) => a.holds(game, set) && b.holds(game, set),
+            Condition::Or(a, b) => a.holds(game, set) || b.holds(game, set),
+            Condition::Any(condition) => game.sets.iter().any(|s| condition.holds(game, Some(s))),
+            Condition::All(condition) => game.sets.iter().all(|s| condition.holds(game, Some(s))),
+            Condition::Compare(a, op, b) => {
+                let (a, b) = (a.of(game, set), b.of(game, set));
+                match op {
+                    Op::Less => a < b,
+                    Op::LessOrEqual => a <= b,
+                    Op::Greater => a > b,
+                    Op::GreaterOrEqual => a >= b,
+                    Op::Equal => a == b,
+                    Op::NotEqual => a != b,
+                }
+            }
+        }
+    }
+}
+
+impl Value {
+    fn of(&self, game: &Game, set: Option<&Set>) -> u64 {
+        match self {
+            Value::Number(n) => *n,
+            Value::Id => game.id.into(),
+            Value::Sets => game.sets.len() as u64,
+            Value::Power => power(game).into(),
+            // The parser only allows these inside a set.
+            Value::Colour(colour) => set.map_or(0, |s| s.count(colour).into()),
+            Value::Total => set.map_or(0, |s| s.cubes().map(|(_, n)| u64::from(n)).sum()),
+            Value::Max(value) => value.per_set(game).max().unwrap_or(0),
+            Value::Min(value) => value.per_set(game).min().unwrap_or(0),
+            Value::Sum(value) => value.per_set(game).sum(),
+        }
+    }
+
+    fn per_set<'a>(&'a self, game: &'a Game) -> impl Iterator<Item = u64> + 'a {
+        game.sets.iter().map(move |set| self.of(game, Some(set)))
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Parser<'a> {
+        Parser {
+            source,
+            rest: source,
+        }
+    }
+
+    /// The next token without consuming it, empty at the end.
+    fn peek(&mut self) -> &'a str {
+        self.rest = self.rest.trim_start();
+        let word = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+        let two_chars = ["<=", ">=", "==", "!="];
+        let len = match self.rest.chars().next() {
+            None => 0,
+            Some(c) if word(c) => self.rest.find(|c| !word(c)).unwrap_or(self.rest.len()),
+            Some(_) if two_chars.iter().any(|op| self.rest.starts_with(op)) => 2,
+            Some(c) => c.len_utf8(),
+        };
+        &self.rest[..len]
+    }
+
+    fn next(&mut self) -> &'a str {
+        let token = self.peek();
+        self.rest = &self.rest[token.len()..];
+        token
+    }
+
+    fn accept(&mut self, token: &str) -> bool {
+        let matches = self.peek() == token;
+        if matches {
+            self.next();
+        }
+        matches
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.accept(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("`{}`", token)))
+        }
+    }
+
+    fn error(&mut self, expected: impl Into<String>) -> ParseError {
+        let token = self.peek();
+        ParseError::at(self.source, token, expected)
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        if self.peek().is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("`and`, `or` or the end of the query"))
+        }
+    }
+
+    fn query(&mut self, in_set: bool) -> Result<Condition, ParseError> {
+        let mut condition = self.and(in_set)?;
+        while self.accept("or") {
+            condition = Condition::Or(Box::new(condition), Box::new(self.and(in_set)?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self, in_set: bool) -> Result<Condition, ParseError> {
+        let mut condition = self.unary(in_set)?;
+        while self.accept("and") {
+            condition = Condition::And(Box::new(condition), Box::new(self.unary(in_set)?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self, in_set: bool) -> Result<Condition, ParseError> {
+        match self.peek() {
+            "not" => {
+                self.next();
+                Ok(Condition::Not(Box::new(self.unary(in_set)?)))
+            }
+            "(" => {
+                self.next();
+                let condition = self.query(in_set)?;
+                self.expect(")")?;
+                Ok(condition)
+            }
+            "true" => {
+                self.next();
+                Ok(Condition::True)
+            }
+            quantifier @ ("any" | "all") => {
+                if in_set {
+                    return Err(self.error("a condition on the set"));
+                }
+                self.next();
+                self.expect("(")?;
+                let condition = Box::new(self.query(true)?);
+                self.expect(")")?;
+                Ok(match quantifier {
+                    "any" => Condition::Any(condition),
+                    _ => Condition::All(condition),
+                })
+            }
+            _ => {
+                let a = self.value(in_set)?;
+                let op = match self.peek() {
+                    "<" => Op::Less,
+                    "<=" => Op::LessOrEqual,
+                    ">" => Op::Greater,
+                    ">=" => Op::GreaterOrEqual,
+                    "==" => Op::Equal,
+                    "!=" => Op::NotEqual,
+                    _ => return Err(self.error("a comparison")),
+                };
+                self.next();
+                let b = self.value(in_set)?;
+                Ok(Condition::Compare(a, op, b))
+            }
+        }
+    }
+
+    fn value(&mut self, in_set: bool) -> Result<Value, ParseError> {
+        let token = self.peek();
+        let value = match token {
+            "id" => Value::Id,
+            "sets" => Value::Sets,
+            "power" => Value::Power,
+            aggregate @ ("max" | "min" | "sum") => {
+                if in_set {
+                    return Err(self.error("a value of the set"));
+                }
+                self.next();
+                self.expect("(")?;
+                let value = Box::new(self.value(true)?);
+                self.expect(")")?;
+                return Ok(match aggregate {
+                    "max" => Value::Max(value),
+                    "min" => Value::Min(value),
+                    _ => Value::Sum(value),
+                });
+            }
+            _ if token.starts_with(|c: char| c.is_ascii_digit()) => {
+                Value::Number(parse_number(self.source, token)?)
+            }
+            _ if !in_set => {
+                return Err(self.error("a game value such as `id`, `power` or `max(red)`"));
+            }
+            "total" => Value::Total,
+            _ if token.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                Value::Colour(token.to_string())
+            }
+            _ => return Err(self.error("a colour, `total` or a number")),
+        };
+        self.next();
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_games;
+
+    fn games() -> Vec<Game> {
+        parse_games(include_str!("example.txt")).unwrap()
+    }
+
+    fn ids(query: &str) -> Vec<u32> {
+        let query = Query::parse(query).unwrap();
+        games()
+            .iter()
+            .filter(|game| query.matches(game))
+            .map(|game| game.id)
+            .collect()
+    }
+
+    fn sum(measure: &str) -> u64 {
+        let measure = Measure::parse(measure).unwrap();
+        games().iter().map(|game| measure.of(game)).sum()
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(ids("id == 1 or id == 2 and id == 3"), [1]);
+        assert_eq!(ids("(id == 1 or id == 2) and id == 2"), [2]);
+        assert_eq!(ids("not id == 1 and id <= 3"), [2, 3]);
+        assert_eq!(ids("not (id == 1 or id == 2)"), [3, 4, 5]);
+        assert_eq!(ids("not not id != 4"), [1, 2, 3, 5]);
+        assert_eq!(ids("true"), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn quantifiers() {
+        assert_eq!(ids("any(red > 10)"), [3, 4]);
+        assert_eq!(ids("all(blue <= 6)"), [1, 2, 3, 5]);
+        assert_eq!(ids("any(total == 2)"), [1, 2]);
+        assert_eq!(ids("any(blue > 5 and red > 5)"), [3, 4]);
+        assert_eq!(ids("all(green > 0) and any(red >= 6)"), [3, 4, 5]);
+    }
+
+    #[test]
+    fn aggregates() {
+        assert_eq!(ids("max(red) >= 12"), [3, 4]);
+        assert_eq!(ids("min(blue) == 0"), [1, 3, 4]);
+        assert_eq!(ids("sum(green) == 4"), [1]);
+        assert_eq!(ids("sum(red) > max(blue)"), [3, 4, 5]);
+        assert_eq!(ids("power > 500"), [3, 4]);
+        assert_eq!(ids("sets == 2"), [5]);
+    }
+
+    #[test]
+    fn colours_only_inside_sets() {
+        assert!(Query::parse("red > 3").is_err());
+        assert!(Query::parse("total > 3").is_err());
+        assert!(Query::parse("any(max(red) > 1)").is_err());
+        assert!(Query::parse("any(any(red > 1))").is_err());
+        assert!(Query::parse("max(max(red)) > 1").is_err());
+        assert!(Measure::parse("red").is_err());
+    }
+
+    #[test]
+    fn syntax_errors() {
+        for query in [
+            "",
+            "id >",
+            "id == 1 and",
+            "any(red > 1",
+            "id = 1",
+            "id == 1 id",
+        ] {
+            assert!(Query::parse(query).is_err(), "{}", query);
+        }
+        let err = Query::parse("any(red > 1").unwrap_err();
+        assert_eq!(err.column(), 12);
+        assert_eq!(err.expected(), "`)`");
+    }
+
+    #[test]
+    fn measures() {
+        assert_eq!(sum("power"), 2286);
+        assert_eq!(sum("id"), 15);
+        assert_eq!(sum("max(red)"), 45);
+        assert_eq!(sum("sum(total)"), 159);
+        assert_eq!(sum("7"), 35);
+    }
+}
@@ -0,0 +1,195 @@
+use common::{parse_number, ParseError};
+
+use crate::{Game, Set};
+
+/// Colours of the puzzle's cubes.
+pub const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// Parses a game log strictly: every line must read `Game N: ...` with ids
+/// counting up from 1, every set must hold at least one `count colour` entry,
+/// and every colour must be one of `colours` and appear at most once per set.
+/// All problems are reported, not just the first.
+pub fn parse_games_strict(input: &str, colours: &[&str]) -> Result<Vec<Game>, Vec<ParseError>> {
+    let mut games = Vec::new();
+    let mut errors = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let mut line_errors = Vec::new();
+        let game = parse_game(line, i as u32 + 1, colours, &mut line_errors);
+        errors.extend(line_errors.into_iter().map(|e| e.within(input, line)));
+        games.extend(game);
+    }
+    if errors.is_empty() {
+        Ok(games)
+    } else {
+        Err(errors)
+    }
+}
+
+fn parse_game(
+    line: &str,
+    expected_id: u32,
+    colours: &[&str],
+    errors: &mut Vec<ParseError>,
+) -> Option<Game> {
+    let Some((header, sets)) = line.split_once(':') else {
+        errors.push(ParseError::at_end(line, "`:`"));
+        return None;
+    };
+    let id = match header.strip_prefix("Game ") {
+        Some(id) if !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()) => {
+            match parse_number(line, id) {
+                Ok(number) if number == expected_id => Some(number),
+                Ok(_) => {
+                    let expected = format!("game id {}", expected_id);
+                    errors.push(ParseError::at(line, id, expected));
+                    None
+                }
+                Err(err) => {
+                    errors.push(err);
+                    None
+                }
+            }
+        }
+        _ => {
+            errors.push(ParseError::at(line, header, "`Game <id>`"));
+            None
+        }
+    };
+
+    let sets: Vec<_> = sets
+        .split(';')
+        .map(|set| parse_set(line, set, colours, errors))
+        .collect();
+    let sets = sets.into_iter().collect::<Option<Vec<_>>>()?;
+    Some(Game { id: id?, sets })
+}
+
+fn parse_set(line: &str, set: &str, colours: &[&str], errors: &mut Vec<ParseError>) -> Option<Set> {
+    if set.trim().is_empty() {
+        errors.push(ParseError::at(line, set, "a set of cubes"));
+        return None;
+    }
+
+    let before = errors.len();
+    let mut cubes = Vec::new();
+    for entry in set.split(',') {
+        let mut words = entry.split_whitespace();
+        let Some(count) = words.next() else {
+            errors.push(ParseError::at(line, entry, "`<count> <colour>`"));
+            continue;
+        };
+        let count: Option<u32> = parse_number(line, count)
+            .map_err(|err| errors.push(err))
+            .ok();
+        let Some(colour) = words.next() else {
+            errors.push(ParseError::at_end(entry, "a colour").within(line, entry));
+            continue;
+        };
+        if let Some(extra) = words.next() {
+            errors.push(ParseError::at(line, extra, "`,` or `;`"));
+        }
+        if !colours.contains(&colour) {
+            let expected = format!("one of {}", colours.join(", "));
+            errors.push(ParseError::at(line, colour, expected));
+        } else if cubes.iter().any(|(c, _)| c == colour) {
+            errors.push(ParseError::at(line, colour, "a colour not yet in this set"));
+        }
+        cubes.push((colour.to_string(), count.unwrap_or(0)));
+    }
+
+    (errors.len() == before).then_some(Set { cubes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Line, column and expected text of every error in `input`.
+    fn errors(input: &str) -> Vec<(usize, usize, String)> {
+        parse_games_strict(input, &COLOURS)
+            .err()
+            .expect("the input has errors")
+            .iter()
+            .map(|err| (err.line(), err.column(), err.expected().to_string()))
+            .collect()
+    }
+
+    fn error(line: usize, column: usize, expected: &str) -> (usize, usize, String) {
+        (line, column, expected.to_string())
+    }
+
+    #[test]
+    fn accepts_the_example() {
+        let games = parse_games_strict(include_str!("example.txt"), &COLOURS).unwrap();
+        assert_eq!(games.len(), 5);
+        assert_eq!(games[2].sets[0].count("red"), 20);
+    }
+
+    #[test]
+    fn colours() {
+        assert_eq!(
+            errors("Game 1: 3 red, 4 red"),
+            [error(1, 18, "a colour not yet in this set")]
+        );
+        assert_eq!(
+            errors("Game 1: 3 purple; 2 blue"),
+            [error(1, 11, "one of red, green, blue")]
+        );
+        assert!(parse_games_strict("Game 1: 3 red; 4 red", &COLOURS).is_ok());
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(errors("Game 1: x red"), [error(1, 9, "number")]);
+        assert_eq!(
+            errors("Game 1: red"),
+            [error(1, 9, "number"), error(1, 12, "a colour")]
+        );
+        assert_eq!(
+            errors("Game 1: 3 red, , 4 blue"),
+            [error(1, 15, "`<count> <colour>`")]
+        );
+        assert_eq!(errors("Game 1: 3 red blue"), [error(1, 15, "`,` or `;`")]);
+    }
+
+    #[test]
+    fn empty_sets_and_trailing_separators() {
+        assert_eq!(
+            errors("Game 1: 3 red;; 4 blue"),
+            [error(1, 15, "a set of cubes")]
+        );
+        assert_eq!(errors("Game 1:"), [error(1, 8, "a set of cubes")]);
+        assert_eq!(errors("Game 1: 3 red;"), [error(1, 15, "a set of cubes")]);
+        assert_eq!(
+            errors("Game 1: 3 red,"),
+            [error(1, 15, "`<count> <colour>`")]
+        );
+    }
+
+    #[test]
+    fn headers_and_ids() {
+        assert_eq!(errors("Gme 1: 3 red"), [error(1, 1, "`Game <id>`")]);
+        assert_eq!(errors("Game one: 3 red"), [error(1, 1, "`Game <id>`")]);
+        assert_eq!(errors("Game 1 3 red"), [error(1, 13, "`:`")]);
+        assert_eq!(
+            errors("Game 1: 1 red\nGame 3: 1 red\nGame 3: 1 red"),
+            [error(2, 6, "game id 2")]
+        );
+        assert_eq!(errors("Game 2: 1 red"), [error(1, 6, "game id 1")]);
+    }
+
+    #[test]
+    fn collects_every_error_in_the_file() {
+        let input = "Game 1: 3 red, 2 red; 1 pink\nGame 2: 4 blue\nGame 4: 1 green;\nGame 4 2 red";
+        assert_eq!(
+            errors(input),
+            [
+                error(1, 18, "a colour not yet in this set"),
+                error(1, 25, "one of red, green, blue"),
+                error(3, 6, "game id 3"),
+                error(3, 17, "a set of cubes"),
+                error(4, 13, "`:`"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use crate::Symbol;
+
+/// How the numbers touching a gear combine into its ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reducer {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Reducer {
+    /// Combines the numbers, or returns `None` if the result does not fit a
+    /// `u64`.
+    pub fn reduce(self, numbers: &[u32]) -> Option<u64> {
+        let mut numbers = numbers.iter().map(|&n| u64::from(n));
+        match self {
+            Reducer::Product => numbers.try_fold(1, u64::checked_mul),
+            Reducer::Sum => numbers.try_fold(0, u64::checked_add),
+            Reducer::Max => Some(numbers.max().unwrap_or(0)),
+        }
+    }
+}
+
+impl FromStr for Reducer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Reducer, String> {
+        match s {
+            "product" => Ok(Reducer::Product),
+            "sum" => Ok(Reducer::Sum),
+            "max" => Ok(Reducer::Max),
+            _ => Err(format!("expected product, sum or max, found `{}`", s)),
+        }
+    }
+}
+
+/// Which symbols are gears: one of `symbols` touching between `min` and
+/// `max` numbers, both inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub min: usize,
+    pub max: usize,
+    pub reducer: Reducer,
+}
+
+impl GearRule {
+    /// Checks that some number of numbers lies between `min` and `max`, and
+    /// that every gear touches a number when they are multiplied.
+    pub fn new(
+        symbols: Vec<char>,
+        min: usize,
+        max: usize,
+        reducer: Reducer,
+    ) -> Result<GearRule, String> {
+        if min > max {
+            return Err(format!(
+                "gears cannot touch at least {} and at most {} numbers",
+                min, max
+            ));
+        }
+        if min == 0 && reducer == Reducer::Product {
+            return Err("gears that are multiplied must touch at least 1 number".to_string());
+        }
+        Ok(GearRule {
+            symbols,
+            min,
+            max,
+            reducer,
+        })
+    }
+
+    /// The gears of part 2: `*` touching exactly two numbers, multiplied.
+    pub fn part2() -> GearRule {
+        GearRule {
+            symbols: vec!['*'],
+            min: 2,
+            max: 2,
+            reducer: Reducer::Product,
+        }
+    }
+
+    pub fn is_gear(&self, symbol: &Symbol) -> bool {
+        self.symbols.contains(&symbol.symbol)
+            && (self.min..=self.max).contains(&symbol.numbers.len())
+    }
+}
+
+/// Every gear among the symbols with its ratio, or the first gear whose
+/// ratio does not fit a `u64`.
+pub fn find_gears<'a>(
+    symbols: &'a [Symbol],
+    rule: &GearRule,
+) -> Result<Vec<(&'a Symbol, u64)>, &'a Symbol> {
+    symbols
+        .iter()
+        .filter(|symbol| rule.is_gear(symbol))
+        .map(|symbol| Ok((symbol, rule.reducer.reduce(&symbol.numbers).ok_or(symbol)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{extract_numbers, find_symbols, Blanks};
+    use common::Grid;
+
+    const SCHEMATIC: &str = include_str!("example.txt");
+
+    fn ratios(schematic: &str, rule: &GearRule) -> Vec<((usize, usize), u64)> {
+        let grid = Grid::parse(schematic).unwrap();
+        let blanks = Blanks::default();
+        let numbers = extract_numbers(&grid, &blanks).unwrap();
+        let symbols = find_symbols(&grid, &blanks, &numbers);
+        find_gears(&symbols, rule)
+            .unwrap()
+            .into_iter()
+            .map(|(gear, ratio)| (gear.cell, ratio))
+            .collect()
+    }
+
+    #[test]
+    fn part2_gears() {
+        assert_eq!(
+            ratios(SCHEMATIC, &GearRule::part2()),
+            [((1, 3), 16345), ((8, 5), 451490)]
+        );
+    }
+
+    #[test]
+    fn hash_touching_exactly_three_numbers() {
+        let schematic = "1.2\n.#.\n..3\n#4.";
+        let rule = GearRule::new(vec!['#'], 3, 3, Reducer::Product).unwrap();
+        assert_eq!(ratios(schematic, &rule), [((1, 1), 6)]);
+    }
+
+    #[test]
+    fn summed_gears() {
+        let rule = GearRule::new(vec!['*'], 1, usize::MAX, Reducer::Sum).unwrap();
+        assert_eq!(
+            ratios(SCHEMATIC, &rule),
+            [((1, 3), 467 + 35), ((4, 3), 617), ((8, 5), 755 + 598)]
+        );
+    }
+
+    #[test]
+    fn products_that_overflow() {
+        let schematic = "4000000000.4000000000\n..........*..........\n..........4000000000.";
+        let rule = GearRule::new(vec!['*'], 1, 3, Reducer::Product).unwrap();
+        let grid = Grid::parse(schematic).unwrap();
+        let blanks = Blanks::default();
+        let numbers = extract_numbers(&grid, &blanks).unwrap();
+        let symbols = find_symbols(&grid, &blanks, &numbers);
+        assert_eq!(find_gears(&symbols, &rule).unwrap_err().cell, (1, 10));
+        assert_eq!(
+            Reducer::Sum.reduce(&[u32::MAX; 3]),
+            Some(3 * u64::from(u32::MAX))
+        );
+    }
+
+    #[test]
+    fn invalid_rules() {
+        assert!(GearRule::new(vec!['*'], 3, 2, Reducer::Sum).is_err());
+        assert!(GearRule::new(vec!['*'], 0, 2, Reducer::Product).is_err());
+        assert!(GearRule::new(vec!['*'], 0, 2, Reducer::Sum).is_ok());
+    }
+}
@@ -0,0 +1,40 @@
+use common::{Grid, ParseError, Solution};
+
+mod gears;
+mod parts;
+mod symbols;
+
+pub use gears::{find_gears, GearRule, Reducer};
+pub use parts::{extract_numbers, Blanks, PartNumber};
+pub use symbols::{find_symbols, symbol_stats, Symbol, SymbolStats};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Grid<char>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(input)
+    }
+
+    fn part1(grid: &Grid<char>) -> Result<u64, ParseError> {
+        Ok(extract_numbers(grid, &Blanks::default())?
+            .iter()
+            .filter(|number| number.is_part())
+            .map(|number| u64::from(number.value))
+            .sum())
+    }
+
+    fn part2(grid: &Grid<char>) -> Result<u64, ParseError> {
+        let blanks = Blanks::default();
+        let numbers = extract_numbers(grid, &blanks)?;
+        let symbols = find_symbols(grid, &blanks, &numbers);
+        Ok(find_gears(&symbols, &GearRule::part2())
+            .expect("two u32 numbers always multiply within a u64")
+            .into_iter()
+            .map(|(_, ratio)| ratio)
+            .sum())
+    }
+}
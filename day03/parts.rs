@@ -0,0 +1,156 @@
+use common::{Grid, ParseError};
+use std::ops::Range;
+
+/// A number in the schematic with the symbols around it. It is a part
+/// number if there are any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub value: u32,
+    pub row: usize,
+    pub col_range: Range<usize>,
+    /// Symbols touching the number, diagonals included, row by row.
+    pub adjacent_symbols: Vec<((usize, usize), char)>,
+}
+
+impl PartNumber {
+    pub fn is_part(&self) -> bool {
+        !self.adjacent_symbols.is_empty()
+    }
+}
+
+/// Characters of a schematic that are neither digits nor symbols, just `.`
+/// by default. Everything else that is not a digit is a symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blanks(Vec<char>);
+
+impl Blanks {
+    pub fn new(blanks: impl IntoIterator<Item = char>) -> Blanks {
+        Blanks(blanks.into_iter().collect())
+    }
+
+    pub fn is_symbol(&self, c: char) -> bool {
+        !c.is_ascii_digit() && !self.0.contains(&c)
+    }
+}
+
+impl Default for Blanks {
+    fn default() -> Blanks {
+        Blanks::new(['.'])
+    }
+}
+
+/// Every number in the schematic, row by row. Fails on numbers that do not
+/// fit a `u32`.
+pub fn extract_numbers(grid: &Grid<char>, blanks: &Blanks) -> Result<Vec<PartNumber>, ParseError> {
+    let mut numbers = Vec::new();
+    for (row, cells) in grid.iter_rows().enumerate() {
+        let mut col = 0;
+        while col < cells.len() {
+            if !cells[col].is_ascii_digit() {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            let mut value: u32 = 0;
+            let mut too_large = false;
+            while let Some(digit) = cells.get(col).and_then(|c| c.to_digit(10)) {
+                match value
+                    .checked_mul(10)
+                    .and_then(|value| value.checked_add(digit))
+                {
+                    Some(next) => value = next,
+                    None => too_large = true,
+                }
+                col += 1;
+            }
+            if too_large {
+                return Err(number_error(cells, row, start..col));
+            }
+            numbers.push(PartNumber {
+                value,
+                row,
+                adjacent_symbols: adjacent_symbols(grid, blanks, row, start..col),
+                col_range: start..col,
+            });
+        }
+    }
+    Ok(numbers)
+}
+
+/// Error about the number in `cols` of the given row of the schematic.
+fn number_error(cells: &[char], row: usize, cols: Range<usize>) -> ParseError {
+    let line: String = cells.iter().collect();
+    let offset = |col: usize| cells[..col].iter().map(|c| c.len_utf8()).sum::<usize>();
+    let number = &line[offset(cols.start)..offset(cols.end)];
+    ParseError::at(&line, number, format!("a number up to {}", u32::MAX)).after_lines(row)
+}
+
+fn adjacent_symbols(
+    grid: &Grid<char>,
+    blanks: &Blanks,
+    row: usize,
+    cols: Range<usize>,
+) -> Vec<((usize, usize), char)> {
+    grid.region(
+        row.saturating_sub(1)..row + 2,
+        cols.start.saturating_sub(1)..cols.end + 1,
+    )
+    .filter(|&(_, &c)| blanks.is_symbol(c))
+    .map(|(cell, &c)| (cell, c))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(schematic: &str) -> Vec<PartNumber> {
+        extract_numbers(&Grid::parse(schematic).unwrap(), &Blanks::default()).unwrap()
+    }
+
+    #[test]
+    fn values_and_columns() {
+        let found = numbers("467..114\n...*....\n..35.633");
+        let values: Vec<_> = found
+            .iter()
+            .map(|number| (number.value, number.row, number.col_range.clone()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                (467, 0, 0..3),
+                (114, 0, 5..8),
+                (35, 2, 2..4),
+                (633, 2, 5..8)
+            ]
+        );
+    }
+
+    #[test]
+    fn symbols_on_the_edges_of_the_grid() {
+        let found = numbers("#1..\n...2\n3..$\n..4.");
+        assert_eq!(found[0].adjacent_symbols, [((0, 0), '#')]);
+        assert_eq!(found[1].adjacent_symbols, [((2, 3), '$')]);
+        assert_eq!(found[2].adjacent_symbols, []);
+        assert_eq!(found[3].adjacent_symbols, [((2, 3), '$')]);
+    }
+
+    #[test]
+    fn numbers_without_symbols_are_not_parts() {
+        let found = numbers("12...\n.....\n..*7.");
+        assert!(!found[0].is_part());
+        assert!(found[1].is_part());
+        assert_eq!(found[1].adjacent_symbols, [((2, 2), '*')]);
+    }
+
+    #[test]
+    fn numbers_that_overflow() {
+        assert_eq!(numbers("4294967295")[0].value, u32::MAX);
+
+        let grid = Grid::parse("1............\n.é*4294967296").unwrap();
+        let err = extract_numbers(&grid, &Blanks::default()).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 4));
+        assert_eq!(err.found(), "`4294967296`");
+        assert_eq!(err.expected(), "a number up to 4294967295");
+    }
+}
@@ -0,0 +1,135 @@
+use common::Grid;
+use std::collections::BTreeMap;
+
+use crate::{Blanks, PartNumber};
+
+/// A symbol of the schematic with the numbers touching it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub cell: (usize, usize),
+    /// Values of the adjacent numbers, in reading order.
+    pub numbers: Vec<u32>,
+}
+
+/// What one symbol character does across the schematic.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolStats {
+    /// How often the symbol occurs.
+    pub count: usize,
+    /// Sum of the numbers touching at least one occurrence of the symbol.
+    pub part_sum: u32,
+    /// How many occurrences touch each number of numbers.
+    pub by_adjacent: BTreeMap<usize, usize>,
+}
+
+/// Every symbol in the schematic, row by row, given the schematic's numbers.
+pub fn find_symbols(grid: &Grid<char>, blanks: &Blanks, numbers: &[PartNumber]) -> Vec<Symbol> {
+    let mut adjacent: BTreeMap<(usize, usize), Vec<u32>> = BTreeMap::new();
+    for number in numbers {
+        for &(cell, _) in &number.adjacent_symbols {
+            adjacent.entry(cell).or_default().push(number.value);
+        }
+    }
+
+    grid.cells()
+        .filter(|&(_, &c)| blanks.is_symbol(c))
+        .map(|(cell, &symbol)| Symbol {
+            symbol,
+            cell,
+            numbers: adjacent.remove(&cell).unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Statistics of every symbol character, keyed by the character.
+pub fn symbol_stats(numbers: &[PartNumber], symbols: &[Symbol]) -> BTreeMap<char, SymbolStats> {
+    let mut stats: BTreeMap<char, SymbolStats> = BTreeMap::new();
+    for symbol in symbols {
+        let entry = stats.entry(symbol.symbol).or_default();
+        entry.count += 1;
+        *entry.by_adjacent.entry(symbol.numbers.len()).or_insert(0) += 1;
+    }
+    for number in numbers {
+        let mut touching: Vec<char> = number.adjacent_symbols.iter().map(|&(_, c)| c).collect();
+        touching.sort_unstable();
+        touching.dedup();
+        for symbol in touching {
+            stats.entry(symbol).or_default().part_sum += number.value;
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extract_numbers;
+
+    fn stats(schematic: &str, blanks: &Blanks) -> BTreeMap<char, SymbolStats> {
+        let grid = Grid::parse(schematic).unwrap();
+        let numbers = extract_numbers(&grid, blanks).unwrap();
+        let symbols = find_symbols(&grid, blanks, &numbers);
+        symbol_stats(&numbers, &symbols)
+    }
+
+    fn by_adjacent(counts: &[(usize, usize)]) -> BTreeMap<usize, usize> {
+        counts.iter().copied().collect()
+    }
+
+    #[test]
+    fn example_stats() {
+        let stats = stats(include_str!("example.txt"), &Blanks::default());
+        assert_eq!(stats.keys().collect::<String>(), "#$*+");
+
+        let gears = &stats[&'*'];
+        assert_eq!(gears.count, 3);
+        assert_eq!(gears.part_sum, 467 + 35 + 617 + 755 + 598);
+        assert_eq!(gears.by_adjacent, by_adjacent(&[(1, 1), (2, 2)]));
+
+        assert_eq!(stats[&'#'].part_sum, 633);
+        assert_eq!(stats[&'$'].part_sum, 664);
+        assert_eq!(stats[&'+'].part_sum, 592);
+        assert_eq!(stats[&'+'].by_adjacent, by_adjacent(&[(1, 1)]));
+    }
+
+    #[test]
+    fn numbers_count_once_per_symbol_character() {
+        let stats = stats("1*2\n*..\n..&", &Blanks::default());
+        assert_eq!(stats[&'*'].count, 2);
+        assert_eq!(stats[&'*'].part_sum, 3);
+        assert_eq!(stats[&'*'].by_adjacent, by_adjacent(&[(1, 1), (2, 1)]));
+        assert_eq!(stats[&'&'].part_sum, 0);
+        assert_eq!(stats[&'&'].by_adjacent, by_adjacent(&[(0, 1)]));
+    }
+
+    #[test]
+    fn custom_blanks() {
+        let schematic = "12_.\n_@3_\n....";
+        let found = stats(schematic, &Blanks::new("._".chars()));
+        assert_eq!(found.keys().collect::<String>(), "@");
+        assert_eq!(found[&'@'].part_sum, 15);
+        assert_eq!(found[&'@'].by_adjacent, by_adjacent(&[(2, 1)]));
+
+        let found = stats(schematic, &Blanks::default());
+        assert_eq!(found.keys().collect::<String>(), "@_");
+        assert_eq!(found[&'_'].count, 3);
+        assert_eq!(found[&'_'].part_sum, 15);
+    }
+
+    #[test]
+    fn find_symbols_lists_the_numbers_in_reading_order() {
+        let grid = Grid::parse("5.6\n.*.\n7..").unwrap();
+        let blanks = Blanks::default();
+        let numbers = extract_numbers(&grid, &blanks).unwrap();
+        let symbols = find_symbols(&grid, &blanks, &numbers);
+        assert_eq!(
+            symbols,
+            [Symbol {
+                symbol: '*',
+                cell: (1, 1),
+                numbers: vec![5, 6, 7],
+            }]
+        );
+    }
+}
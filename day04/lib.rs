@@ -0,0 +1,128 @@
+use common::{parse_lines, parse_number, ParseError, Solution};
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::convert::TryInto;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = HashMap<u32, Card>;
+    type Answer1 = usize;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<HashMap<u32, Card>, ParseError> {
+        parse_cards(input)
+    }
+
+    fn part1(cards: &HashMap<u32, Card>) -> Result<usize, ParseError> {
+        Ok(cards.values().map(|card| card.worth()).sum())
+    }
+
+    fn part2(cards: &HashMap<u32, Card>) -> Result<u32, ParseError> {
+        let mut cards = cards.clone();
+        process_winnings(&mut cards);
+        Ok(cards.into_values().map(|card| card.count).sum())
+    }
+}
+
+fn process_winnings(cards: &mut HashMap<u32, Card>) {
+    for i in 1..=(cards.len() as u32) {
+        let to_copy = cards_to_copy(cards.get(&i).unwrap());
+        let copy_count = cards.get(&i).unwrap().count;
+        to_copy.into_iter().for_each(|id| {
+            if let Some(card) = cards.get_mut(&id) {
+                card.count += copy_count;
+            }
+        });
+    }
+}
+
+fn cards_to_copy(card: &Card) -> Vec<u32> {
+    let mut cards = Vec::new();
+    for i in 1..=card.matching_number_count {
+        cards.push(card.id + i as u32)
+    }
+    cards
+}
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    id: u32,
+    count: u32,
+    _winning_numbers: HashSet<u32>,
+    _numbers: HashSet<u32>,
+    matching_number_count: usize,
+}
+
+/// Parses the cards, whose ids must count up from 1 as copies are won of
+/// the cards following a card.
+fn parse_cards(input: &str) -> Result<HashMap<u32, Card>, ParseError> {
+    let mut cards = HashMap::new();
+    for (card, line) in parse_lines(input, parse_card)?
+        .into_iter()
+        .zip(input.lines())
+    {
+        let expected = cards.len() as u32 + 1;
+        if card.id != expected {
+            let expected = format!("card id {}", expected);
+            return Err(ParseError::at(line, card_id(line), expected).within(input, line));
+        }
+        cards.insert(card.id, card);
+    }
+    Ok(cards)
+}
+
+fn parse_card(line: &str) -> Result<Card, ParseError> {
+    let (_, number_parts) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at_end(line, "`:`"))?;
+    let id: u32 = parse_number(line, card_id(line))?;
+    let (winning_numbers, numbers) = number_parts
+        .split_once('|')
+        .ok_or_else(|| ParseError::at_end(line, "`|`"))?;
+    let winning_numbers = parse_numbers(line, winning_numbers)?;
+    let numbers = parse_numbers(line, numbers)?;
+    let matching_number_count = count_matching_numbers(&numbers, &winning_numbers);
+
+    Ok(Card {
+        id,
+        count: 1,
+        _winning_numbers: winning_numbers,
+        _numbers: numbers,
+        matching_number_count,
+    })
+}
+
+/// The id in the `Card <id>:` header of a line.
+fn card_id(line: &str) -> &str {
+    let header = line.split_once(':').map_or(line, |(header, _)| header);
+    header.split(' ').next_back().unwrap_or(header)
+}
+
+fn parse_numbers(line: &str, numbers: &str) -> Result<HashSet<u32>, ParseError> {
+    numbers
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|s| parse_number(line, s))
+        .collect()
+}
+
+fn count_matching_numbers(numbers: &HashSet<u32>, winning_numbers: &HashSet<u32>) -> usize {
+    numbers
+        .iter()
+        .filter(|n| winning_numbers.contains(n))
+        .count()
+}
+
+impl Card {
+    fn worth(&self) -> usize {
+        if self.matching_number_count == 0 {
+            0
+        } else {
+            2_u64
+                .pow((self.matching_number_count - 1).try_into().unwrap())
+                .try_into()
+                .unwrap()
+        }
+    }
+}
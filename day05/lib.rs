@@ -0,0 +1,256 @@
+use common::{parse_number, ParseError, Solution};
+use rayon::prelude::*;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Almanac, ParseError> {
+        parse_almanac(input)
+    }
+
+    fn part1(almanac: &Almanac) -> Result<u64, ParseError> {
+        Ok(almanac.lowest_location(&almanac.seeds))
+    }
+
+    fn part2(almanac: &Almanac) -> Result<u64, ParseError> {
+        Ok(almanac.lowest_location(&almanac.seed_ranges))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MapRange {
+    destination_range_start: u64,
+    source_range_start: u64,
+    range_length: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Map {
+    name: String,
+    ranges: Vec<MapRange>,
+}
+
+/// The seeds line is kept in both readings: as individual seeds (part 1) and
+/// as `start length` pairs (part 2).
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<(u64, u64)>,
+    seed_ranges: Vec<(u64, u64)>,
+    seed_to_soil: Map,
+    soil_to_fertilizer: Map,
+    fertilizer_to_water: Map,
+    water_to_light: Map,
+    light_to_temperature: Map,
+    temperature_to_humidity: Map,
+    humidity_to_location: Map,
+}
+
+fn parse_almanac(input: &str) -> Result<Almanac, ParseError> {
+    let parts: Vec<_> = input.split("\n\n").collect();
+    let seeds = parse_seeds(input, parts[0])?;
+    let seed_ranges = parse_seeds_range(input, parts[0])?;
+    let maps = parts[1..]
+        .iter()
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| parse_map(input, p))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let seed_to_soil = find_map(input, &maps, "seed-to-soil")?;
+    let soil_to_fertilizer = find_map(input, &maps, "soil-to-fertilizer")?;
+    let fertilizer_to_water = find_map(input, &maps, "fertilizer-to-water")?;
+    let water_to_light = find_map(input, &maps, "water-to-light")?;
+    let light_to_temperature = find_map(input, &maps, "light-to-temperature")?;
+    let temperature_to_humidity = find_map(input, &maps, "temperature-to-humidity")?;
+    let humidity_to_location = find_map(input, &maps, "humidity-to-location")?;
+
+    Ok(Almanac {
+        seeds,
+        seed_ranges,
+        seed_to_soil,
+        soil_to_fertilizer,
+        fertilizer_to_water,
+        water_to_light,
+        light_to_temperature,
+        temperature_to_humidity,
+        humidity_to_location,
+    })
+}
+
+fn find_map(input: &str, maps: &[Map], name: &str) -> Result<Map, ParseError> {
+    maps.iter()
+        .find(|m| m.name == name)
+        .cloned()
+        .ok_or_else(|| ParseError::at_end(input, format!("`{}` map", name)))
+}
+
+fn parse_seeds(input: &str, line: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    seed_numbers(input, line)?
+        .map(|s| parse_number(input, s))
+        .map(|s| s.map(|s| (s, s)))
+        .collect()
+}
+
+fn parse_seeds_range(input: &str, line: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let mut numbers = seed_numbers(input, line)?;
+
+    let mut seed_ranges: Vec<(u64, u64)> = Vec::new();
+    while let Some(start) = numbers.next() {
+        let start: u64 = parse_number(input, start)?;
+        let Some(length) = numbers.next() else {
+            return Err(ParseError::at(input, &line[line.len()..], "range length"));
+        };
+        let end = match parse_number::<u64>(input, length)? {
+            0 => return Err(ParseError::at(input, length, "range length above 0")),
+            n => start.checked_add(n - 1).ok_or_else(|| {
+                ParseError::at(input, length, "range length that stays below 2^64")
+            })?,
+        };
+
+        seed_ranges.push((start, end));
+    }
+    Ok(seed_ranges)
+}
+
+fn seed_numbers<'a>(
+    input: &str,
+    line: &'a str,
+) -> Result<impl Iterator<Item = &'a str>, ParseError> {
+    match line.split_once(':') {
+        Some(("seeds", numbers)) => Ok(numbers.split(' ').filter(|s| !s.is_empty())),
+        Some((header, _)) => Err(ParseError::at(input, header, "`seeds`")),
+        None => Err(ParseError::at(input, &line[line.len()..], "`:`")),
+    }
+}
+
+fn parse_map(input: &str, block: &str) -> Result<Map, ParseError> {
+    let mut lines = block.lines();
+    let header = lines.next().unwrap_or(block);
+    let name = header.split(' ').next().unwrap_or(header);
+    if name.is_empty() {
+        return Err(ParseError::at(input, name, "map name"));
+    }
+    let ranges = lines
+        .map(|line| parse_map_range(input, line))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Map {
+        name: name.to_string(),
+        ranges,
+    })
+}
+
+fn parse_map_range(input: &str, line: &str) -> Result<MapRange, ParseError> {
+    match line.split(' ').collect::<Vec<_>>()[..] {
+        [destination_range_start, source_range_start, range_length] => Ok(MapRange {
+            destination_range_start: parse_number(input, destination_range_start)?,
+            source_range_start: parse_number(input, source_range_start)?,
+            range_length: parse_number(input, range_length)?,
+        }),
+        _ => Err(ParseError::at(input, line, "three numbers")),
+    }
+}
+
+impl MapRange {
+    fn is_within(&self, source: u64) -> bool {
+        source >= self.source_range_start && source < self.source_range_start + self.range_length
+    }
+
+    fn map(&self, source: u64) -> u64 {
+        let diff = source - self.source_range_start;
+        self.destination_range_start + diff
+    }
+}
+
+impl Map {
+    fn source_to_destination(&self, source: u64) -> u64 {
+        match self.matching_range(source) {
+            None => source,
+            Some(range) => range.map(source),
+        }
+    }
+
+    fn matching_range(&self, source: u64) -> Option<&MapRange> {
+        self.ranges.iter().find(|range| range.is_within(source))
+    }
+}
+
+impl Almanac {
+    fn lowest_location(&self, seed_ranges: &[(u64, u64)]) -> u64 {
+        let mut lowest: u64 = u64::MAX;
+        for &(start, end) in seed_ranges {
+            let location: u64 = (start..=end)
+                .into_par_iter()
+                .map(|seed| self.seed_to_soil.source_to_destination(seed))
+                .map(|soil| self.soil_to_fertilizer.source_to_destination(soil))
+                .map(|fertilizer| self.fertilizer_to_water.source_to_destination(fertilizer))
+                .map(|water| self.water_to_light.source_to_destination(water))
+                .map(|light| self.light_to_temperature.source_to_destination(light))
+                .map(|temperature| {
+                    self.temperature_to_humidity
+                        .source_to_destination(temperature)
+                })
+                .map(|humidity| self.humidity_to_location.source_to_destination(humidity))
+                .min()
+                .unwrap();
+            if location < lowest {
+                lowest = location;
+            }
+        }
+        lowest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed_ranges(seeds: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+        parse_seeds_range(seeds, seeds)
+    }
+
+    #[test]
+    fn seed_ranges_include_both_ends() {
+        assert_eq!(
+            seed_ranges("seeds: 79 14 55 1").unwrap(),
+            [(79, 92), (55, 55)]
+        );
+        assert_eq!(
+            seed_ranges("seeds: 18446744073709551615 1").unwrap(),
+            [(u64::MAX, u64::MAX)]
+        );
+    }
+
+    #[test]
+    fn empty_seed_ranges() {
+        let err = seed_ranges("seeds: 79 14 55 0").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 17));
+        assert_eq!(err.expected(), "range length above 0");
+    }
+
+    #[test]
+    fn seed_ranges_past_the_last_number() {
+        let err = seed_ranges("seeds: 18446744073709551615 2").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 29));
+        assert_eq!(err.expected(), "range length that stays below 2^64");
+    }
+
+    #[test]
+    fn seed_range_without_length() {
+        let err = seed_ranges("seeds: 79 14 55").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 16));
+        assert_eq!(err.expected(), "range length");
+        assert_eq!(err.found(), "end of input");
+    }
+
+    #[test]
+    fn zero_length_ranges_fail_to_parse() {
+        let input = include_str!("example.txt").replacen("55 13", "55 0", 1);
+        assert!(input.starts_with("seeds: 79 14 55 0\n"));
+        let err = Day05::parse(&input).unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 17));
+    }
+}
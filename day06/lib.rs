@@ -0,0 +1,103 @@
+use common::{parse_number, ParseError, Solution};
+use std::convert::TryInto;
+use std::iter::zip;
+
+pub struct Day06;
+
+/// The sheet read either as separate races (part 1) or, ignoring the spaces
+/// between the numbers, as one long race (part 2).
+#[derive(Debug)]
+pub struct Races {
+    races: Vec<(u64, u64)>,
+    single_race: (u64, u64),
+}
+
+impl Solution for Day06 {
+    type Input = Races;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Races, ParseError> {
+        Ok(Races {
+            races: parse_races(input)?,
+            single_race: parse_single_race(input)?,
+        })
+    }
+
+    fn part1(races: &Races) -> Result<u64, ParseError> {
+        let winning_counts: Vec<_> = races.races.iter().copied().map(winning_count).collect();
+        Ok(winning_counts.iter().product())
+    }
+
+    fn part2(races: &Races) -> Result<u64, ParseError> {
+        Ok(winning_count(races.single_race))
+    }
+}
+
+fn parse_single_race(input: &str) -> Result<(u64, u64), ParseError> {
+    let (time, distance) = race_lines(input)?;
+    let time = parse_single_number(input, time)?;
+    let distance = parse_single_number(input, distance)?;
+    Ok((time, distance))
+}
+
+fn parse_races(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let (time, distance) = race_lines(input)?;
+    let times = parse_numbers(input, time)?;
+    let distances = parse_numbers(input, distance)?;
+    Ok(zip(times, distances).collect())
+}
+
+fn race_lines(input: &str) -> Result<(&str, &str), ParseError> {
+    let mut lines = input.lines();
+    let time = lines
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "`Time:` line"))?;
+    let distance = lines
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "`Distance:` line"))?;
+    Ok((time, distance))
+}
+
+fn parse_single_number(input: &str, line: &str) -> Result<u64, ParseError> {
+    let string = line.split(':').next_back().unwrap_or(line);
+    string
+        .replace(' ', "")
+        .parse()
+        .map_err(|_| ParseError::at(input, string.trim(), "number"))
+}
+
+fn parse_numbers(input: &str, line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split(':')
+        .next_back()
+        .unwrap_or(line)
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|s| parse_number(input, s))
+        .collect()
+}
+
+fn distance_travelled(race_length: u64, hold_time: u64) -> u64 {
+    assert!(hold_time <= race_length);
+    let speed = hold_time;
+    let race_time = race_length - hold_time;
+    speed * race_time
+}
+
+fn possible_distances(race_length: u64) -> Vec<u64> {
+    let mut distances = Vec::new();
+    for i in 1..race_length {
+        distances.push(distance_travelled(race_length, i));
+    }
+    distances
+}
+
+fn winning_count(race: (u64, u64)) -> u64 {
+    let (race_length, record_time) = race;
+    possible_distances(race_length)
+        .into_iter()
+        .filter(|d| d > &record_time)
+        .count()
+        .try_into()
+        .unwrap()
+}